@@ -1,4 +1,6 @@
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 
 #[non_exhaustive]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -6,12 +8,34 @@ pub(crate) enum OutputFormatter {
     Diff,
     Json,
     Rdjson,
+    Github,
+    Gitlab,
+    Checkstyle,
+    Junit,
+    Html,
+    Patch,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub(crate) enum RulesFormatter {
+    Text,
+    Json,
 }
 
 impl OutputFormatter {
     pub fn is_diff(&self) -> bool {
         *self == OutputFormatter::Diff
     }
+
+    /// The formats of the `i18n-check` output, `patch` is only for the fixed files.
+    fn i18n_parser() -> impl TypedValueParser<Value = OutputFormatter> {
+        let formats = Self::value_variants()
+            .iter()
+            .filter(|f| **f != OutputFormatter::Patch)
+            .filter_map(|f| f.to_possible_value());
+
+        PossibleValuesParser::new(formats).map(|s| OutputFormatter::from_str(&s, true).unwrap())
+    }
 }
 
 #[derive(Debug, Parser, Clone)]
@@ -72,6 +96,14 @@ pub(crate) struct Cli {
     #[clap(long = "stdin", help = "Input text from <STDIN>")]
     pub stdin: bool,
 
+    #[clap(
+        long,
+        value_name = "PATH",
+        requires = "stdin",
+        help = "The path of the text from <STDIN>, for the file type, the ignore rules and the lint output."
+    )]
+    pub stdin_filename: Option<String>,
+
     #[clap(
         long = "no-diff-bg-color",
         alias = "ndbc",
@@ -79,12 +111,82 @@ pub(crate) struct Cli {
     )]
     pub no_diff_bg_color: bool,
 
+    #[clap(
+        long,
+        value_enum,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "text",
+        help = "Output the lint statistics by rule, file type, directory and the top files, instead of the problems, e.g.: `--stats`, `--stats=json`."
+    )]
+    pub stats: Option<RulesFormatter>,
+
+    #[clap(
+        long,
+        help = "Write the lint report to the file instead of stdout, e.g.: `--format html --output report.html`."
+    )]
+    pub output: Option<String>,
+
+    #[clap(
+        long,
+        help = "With `--fix`, do not write the files, only print the files would be changed, or the unified diff with `--format patch`."
+    )]
+    pub dry_run: bool,
+
+    #[clap(
+        long,
+        requires = "fix",
+        conflicts_with_all = ["stdin", "dry_run"],
+        help = "With `--fix`, prompt to accept, skip, accept all of the rules, or disable the rules by a comment for each change."
+    )]
+    pub interactive: bool,
+
     #[clap(
         long,
         help = "Strict mode will cause warnings to return a non-zero value.",
         default_value = "false"
     )]
     pub strict: bool,
+
+    #[clap(
+        long,
+        value_name = "N",
+        help = "Exit with a non-zero value when the warnings are more than N, overrides `--strict`."
+    )]
+    pub max_warnings: Option<usize>,
+
+    #[clap(
+        long,
+        value_name = "N",
+        help = "Exit with a non-zero value when the errors are more than N.",
+        default_value = "0"
+    )]
+    pub max_errors: usize,
+
+    #[clap(
+        long,
+        help = "Report the files that can't be parsed, but do not exit with a non-zero value for them."
+    )]
+    pub allow_parse_errors: bool,
+
+    #[clap(
+        long,
+        help = "Report the disable directives that suppress nothing, have unknown rules, or are not enabled again, for lint."
+    )]
+    pub report_unused_disable_directives: bool,
+
+    #[clap(
+        long,
+        help = "Format the output again, and report the files that are changed by the second format."
+    )]
+    pub check_idempotent: bool,
+
+    #[clap(
+        long,
+        help = "The max format passes for `--check-idempotent`, more than 2 to format until the output stops changing, and write it with `--fix`.",
+        default_value = "2"
+    )]
+    pub max_iterations: usize,
 }
 
 #[derive(Debug, Subcommand, Clone)]
@@ -113,9 +215,83 @@ pub(crate) enum Commands {
     Update {},
     #[command(name = "server", about = "Start AutoCorrect LSP server.")]
     Server {},
+    #[command(
+        name = "dictionaries",
+        about = "List the built-in spellcheck dictionaries, or show the words of a dictionary."
+    )]
+    Dictionaries {
+        #[clap(help = "The dictionary name to show words.")]
+        name: Option<String>,
+    },
+    #[command(
+        name = "rules",
+        about = "List the rules with the description and default severity."
+    )]
+    Rules {
+        #[clap(long, help = "Output format.", default_value = "text")]
+        #[arg(value_enum)]
+        format: RulesFormatter,
+    },
+    #[command(
+        name = "explain",
+        about = "Show the description of a rule, and run the examples through the formatter."
+    )]
+    Explain {
+        #[clap(help = "The rule name, e.g.: space-dash")]
+        name: String,
+    },
+    #[command(
+        name = "i18n-check",
+        about = "Check the translation files with the source file, for the missing keys, untranslated values and mismatched placeholders."
+    )]
+    I18nCheck {
+        #[clap(long, help = "The source translation file, e.g.: locales/en.yml")]
+        source: String,
+
+        #[clap(
+            long,
+            help = "The target translation files, e.g.: locales/zh-CN.yml",
+            required = true,
+            num_args = 1..
+        )]
+        target: Vec<String>,
+
+        #[clap(
+            long,
+            help = "Output format.",
+            default_value = "diff",
+            value_parser = OutputFormatter::i18n_parser()
+        )]
+        format: OutputFormatter,
+    },
 }
 
 impl Cli {
+    /// Show the progress dots and the spend time, only for the diff output.
+    ///
+    /// Not for `--stdin`, the stdout is only the output of the stdin for the editors and the hooks.
+    pub fn show_progress(&self) -> bool {
+        !self.quiet
+            && !self.stdin
+            && self.formatter.is_diff()
+            && self.stats.is_none()
+            && !self.interactive
+    }
+
+    /// Check the args that can't be declared by the attributes.
+    pub fn validate(&self) -> Result<(), clap::Error> {
+        if self.formatter == OutputFormatter::Patch
+            && (!self.fix || !self.dry_run || self.lint || self.stdin)
+        {
+            return Err(Self::command().error(
+                ErrorKind::ArgumentConflict,
+                "`--format patch` can only be used with `--fix --dry-run`, and without `--lint` or `--stdin`",
+            ));
+        }
+
+        Ok(())
+    }
+
     pub fn log_level(&self) -> log::LevelFilter {
         if self.debug && !self.quiet {
             log::LevelFilter::Debug
@@ -124,3 +300,57 @@ impl Cli {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(args: &[&str]) -> Result<(), clap::Error> {
+        Cli::parse_from([&["autocorrect"], args].concat()).validate()
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate(&["--lint"]).is_ok());
+        assert!(validate(&["--fix", "--dry-run", "--format", "patch"]).is_ok());
+        assert!(validate(&[
+            "--check-idempotent",
+            "--fix",
+            "--dry-run",
+            "--format",
+            "patch"
+        ])
+        .is_ok());
+
+        let err = validate(&["--lint", "--format", "patch"]).unwrap_err();
+        assert_eq!(ErrorKind::ArgumentConflict, err.kind());
+        assert!(validate(&["--fix", "--format", "patch"]).is_err());
+        assert!(validate(&["--format", "patch"]).is_err());
+        assert!(validate(&["--fix", "--dry-run", "--stdin", "--format", "patch"]).is_err());
+    }
+
+    #[test]
+    fn test_i18n_check_format() {
+        let parse = |format: &str| {
+            Cli::try_parse_from([
+                "autocorrect",
+                "i18n-check",
+                "--source",
+                "en.yml",
+                "--target",
+                "zh-CN.yml",
+                "--format",
+                format,
+            ])
+            .map(|cli| match cli.command {
+                Some(Commands::I18nCheck { format, .. }) => format,
+                _ => unreachable!(),
+            })
+        };
+
+        assert_eq!(OutputFormatter::Diff, parse("diff").unwrap());
+        assert_eq!(OutputFormatter::Html, parse("html").unwrap());
+        assert_eq!(OutputFormatter::Checkstyle, parse("checkstyle").unwrap());
+        assert_eq!(ErrorKind::InvalidValue, parse("patch").unwrap_err().kind());
+    }
+}
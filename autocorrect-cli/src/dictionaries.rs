@@ -0,0 +1,33 @@
+use autocorrect::config::{builtin_dictionaries, builtin_dictionary, SpellcheckWord};
+use owo_colors::OwoColorize;
+
+/// List the built-in spellcheck dictionaries, or print the words of a dictionary.
+pub(crate) fn run(name: Option<&str>) {
+    let Some(name) = name else {
+        log::info!("Built-in spellcheck dictionaries:\n");
+        for dict in builtin_dictionaries() {
+            log::info!(
+                "  {:<14}{:>4} words  {}",
+                dict.name.green(),
+                dict.words().count(),
+                dict.description()
+            );
+        }
+        log::info!(
+            "\nEnable them in .autocorrectrc:\n\n  spellcheck:\n    dictionaries:\n      - programming\n"
+        );
+        return;
+    };
+
+    let Some(dict) = builtin_dictionary(name) else {
+        log::error!("Unknown spellcheck dictionary: {}", name);
+        std::process::exit(1);
+    };
+
+    log::info!("# {}", dict.description());
+    for word in dict.words() {
+        if let SpellcheckWord::Plain(word) = word {
+            log::info!("{}", word);
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use std::fs;
+
+use owo_colors::OwoColorize;
+
+use crate::cli::{Cli, OutputFormatter};
+
+/// Check the target translation files with the source file, and output the results like the lint.
+pub(crate) fn run(cli: &Cli, source: &str, targets: &[String], format: OutputFormatter) {
+    let source_raw = read_file(source);
+
+    let results = targets
+        .iter()
+        .flat_map(|target| {
+            autocorrect::i18n::check(&source_raw, source, &read_file(target), target)
+        })
+        .collect::<Vec<_>>();
+
+    let mut errors_count = 0;
+    let mut warnings_count = 0;
+    for result in results.iter() {
+        errors_count += result.errors_count();
+        warnings_count += result.warnings_count();
+        if result.has_error() {
+            errors_count += 1;
+        }
+    }
+
+    match format {
+        OutputFormatter::Json => {
+            crate::write_report(cli, &autocorrect::json::to_lint_results_json(results));
+        }
+        OutputFormatter::Rdjson => {
+            crate::write_report(cli, &autocorrect::rdjson::to_lint_results_rdjson(results));
+        }
+        OutputFormatter::Gitlab => {
+            crate::write_report(cli, &autocorrect::gitlab::to_lint_results_gitlab(results));
+        }
+        OutputFormatter::Checkstyle => {
+            crate::write_report(
+                cli,
+                &autocorrect::checkstyle::to_lint_results_checkstyle(results),
+            );
+        }
+        OutputFormatter::Junit => {
+            crate::write_report(cli, &autocorrect::junit::to_lint_results_junit(results));
+        }
+        OutputFormatter::Html => {
+            crate::write_report(cli, &autocorrect::html::to_lint_results_html(results));
+        }
+        OutputFormatter::Github => {
+            crate::write_github_summary(&results);
+            crate::write_report(cli, &autocorrect::github::to_lint_results_github(results));
+        }
+        _ => {
+            for result in results.iter() {
+                if result.has_error() {
+                    log::error!("{}: {}", result.filepath, result.error);
+                    continue;
+                }
+                log::info!("{}", result.to_diff(cli.no_diff_bg_color));
+            }
+
+            if errors_count > 0 || warnings_count > 0 {
+                log::info!(
+                    "{}, {}",
+                    format!("Error: {errors_count}").red(),
+                    format!("Warning: {warnings_count}").yellow(),
+                );
+            } else {
+                log::info!("No issues found.");
+            }
+        }
+    }
+
+    let counts = crate::LintCounts {
+        errors: errors_count,
+        warnings: warnings_count,
+        parse_errors: 0,
+    };
+    crate::exit_with(counts.exit_code(cli));
+}
+
+fn read_file(filepath: &str) -> String {
+    fs::read_to_string(filepath).unwrap_or_else(|err| {
+        log::error!("Failed to read: {} error: {}", filepath, err);
+        std::process::exit(1);
+    })
+}
@@ -0,0 +1,433 @@
+//! Interactive fix for `--fix --interactive`, prompt to accept or skip each change, and write only the accepted changes.
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io::{self, BufRead, Write};
+
+use autocorrect::LineResult;
+use owo_colors::OwoColorize;
+
+use crate::cli::Cli;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Choice {
+    Accept,
+    Skip,
+    /// Accept this change, and the later changes of the same rules
+    AcceptRule,
+    /// Insert a comment to disable the rules for the line
+    DisableRule,
+    /// Stop to prompt, the accepted changes are still written
+    Quit,
+}
+
+impl Choice {
+    fn parse(input: &str) -> Option<Self> {
+        match input.trim() {
+            "y" | "yes" => Some(Choice::Accept),
+            "n" | "no" => Some(Choice::Skip),
+            "a" | "all" => Some(Choice::AcceptRule),
+            "d" | "disable" => Some(Choice::DisableRule),
+            "q" | "quit" => Some(Choice::Quit),
+            _ => None,
+        }
+    }
+}
+
+/// The start and end of the line comment for the file type, `None` if the grammar does not parse the comment for the toggle,
+/// or the file type can't have a comment, e.g.: text, json.
+fn comment_for(filetype: &str) -> Option<(&'static str, &'static str)> {
+    match autocorrect::match_filename(filetype).as_str() {
+        "html" | "markdown" => Some(("<!-- ", " -->")),
+        "css" => Some(("/* ", " */")),
+        "yaml" | "ruby" | "elixir" | "python" | "gettext" | "conf" => Some(("# ", "")),
+        "sql" | "lua" => Some(("-- ", "")),
+        "latex" => Some(("% ", "")),
+        "rust" | "go" | "javascript" | "objective_c" | "strings" | "csharp" | "swift" | "java"
+        | "scala" | "kotlin" | "php" | "dart" | "c" | "zig" => Some(("// ", "")),
+        _ => None,
+    }
+}
+
+/// The state of the interactive session, the accepted rules are kept across the files.
+#[derive(Default)]
+pub(crate) struct Session {
+    accepted_rules: HashSet<String>,
+    quit: bool,
+}
+
+impl Session {
+    pub fn is_quit(&self) -> bool {
+        self.quit
+    }
+
+    /// Prompt the changes of a file, and write the accepted changes.
+    pub fn fix_file(&mut self, cli: &Cli, filepath: &str, filetype: &str) {
+        let raw = match fs::read_to_string(filepath) {
+            Ok(raw) => raw,
+            Err(err) => {
+                log::error!("Failed to read: {} error: {}", filepath, err);
+                return;
+            }
+        };
+
+        let stdin = io::stdin();
+        let mut stdout = io::stdout();
+        let out = self.prompt_file(
+            cli,
+            filepath,
+            filetype,
+            &raw,
+            &mut stdin.lock(),
+            &mut stdout,
+        );
+        if out == raw {
+            return;
+        }
+        if let Err(err) = fs::write(filepath, out) {
+            log::error!("Failed to write: {} error: {}", filepath, err);
+        }
+    }
+
+    /// Prompt each change of the lint result, returns the text with the accepted changes.
+    fn prompt_file(
+        &mut self,
+        cli: &Cli,
+        filepath: &str,
+        filetype: &str,
+        raw: &str,
+        input: &mut impl BufRead,
+        output: &mut impl Write,
+    ) -> String {
+        let result = autocorrect::lint_for(raw, filetype);
+        let filepath = filepath.trim_start_matches("./");
+        if result.has_error() {
+            let (line, col) = result.error_position();
+            log::error!("{}:{}:{} {}", filepath, line, col, result.error);
+            return raw.to_owned();
+        }
+
+        let comment = comment_for(filetype);
+        let mut accepted = vec![];
+        let mut disabled: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+
+        for line_result in result.lines.iter() {
+            if self.quit {
+                break;
+            }
+
+            if !line_result.rules.is_empty()
+                && line_result
+                    .rules
+                    .iter()
+                    .all(|rule| self.accepted_rules.contains(rule))
+            {
+                accepted.push(line_result);
+                continue;
+            }
+
+            // The comment can only be inserted before the line where the node starts,
+            // e.g.: not inside a multi-line string.
+            let can_disable = comment.is_some() && line_result.node_line == line_result.line;
+            match self.prompt(cli, filepath, line_result, can_disable, input, output) {
+                Choice::Accept => accepted.push(line_result),
+                Choice::Skip => {}
+                Choice::AcceptRule => {
+                    self.accepted_rules
+                        .extend(line_result.rules.iter().cloned());
+                    accepted.push(line_result);
+                }
+                Choice::DisableRule => {
+                    let rules = disabled.entry(line_result.line).or_default();
+                    for rule in line_result.rules.iter() {
+                        if !rules.contains(rule) {
+                            rules.push(rule.clone());
+                        }
+                    }
+                }
+                Choice::Quit => self.quit = true,
+            }
+        }
+
+        apply(raw, &accepted, &disabled, comment)
+    }
+
+    fn prompt(
+        &mut self,
+        cli: &Cli,
+        filepath: &str,
+        line_result: &LineResult,
+        can_disable: bool,
+        input: &mut impl BufRead,
+        output: &mut impl Write,
+    ) -> Choice {
+        let rules = line_result.rules.join(", ");
+        let _ = writeln!(
+            output,
+            "\n{}:{}:{} {}",
+            filepath,
+            line_result.line,
+            line_result.col,
+            rules.dimmed()
+        );
+        let _ = write!(
+            output,
+            "{}",
+            autocorrect::diff_line_result(line_result, cli.no_diff_bg_color)
+        );
+
+        let mut options = vec!["[y]es", "[n]o"];
+        if !rules.is_empty() {
+            options.push("[a]ll of the rules");
+        }
+        if can_disable {
+            options.push("[d]isable here");
+        }
+        options.push("[q]uit");
+
+        loop {
+            let _ = write!(output, "Accept this change? {}: ", options.join(", "));
+            let _ = output.flush();
+
+            let mut answer = String::new();
+            match input.read_line(&mut answer) {
+                // EOF, stop to prompt
+                Ok(0) | Err(_) => return Choice::Quit,
+                Ok(_) => {}
+            }
+
+            match Choice::parse(&answer) {
+                Some(Choice::AcceptRule) if rules.is_empty() => {}
+                Some(Choice::DisableRule) if !can_disable => {}
+                Some(choice) => return choice,
+                None => {}
+            }
+        }
+    }
+}
+
+/// Apply the accepted changes and insert the disable comments before the lines.
+fn apply(
+    raw: &str,
+    accepted: &[&LineResult],
+    disabled: &BTreeMap<usize, Vec<String>>,
+    comment: Option<(&str, &str)>,
+) -> String {
+    let mut lines = raw.split('\n').map(String::from).collect::<Vec<_>>();
+
+    // Apply from the end of the line, so the cols of the earlier changes are kept
+    let mut accepted = accepted.to_vec();
+    accepted.sort_by_key(|line_result| (line_result.line, line_result.col));
+    for line_result in accepted.iter().rev() {
+        let Some(text) = lines.get_mut(line_result.line.saturating_sub(1)) else {
+            continue;
+        };
+
+        // The col is the count of chars, and the `old` is trimmed, so find it from the col
+        let start = text
+            .char_indices()
+            .nth(line_result.col.saturating_sub(1))
+            .map_or(text.len(), |(i, _)| i);
+        let pos = text[start..]
+            .find(&line_result.old)
+            .map(|i| start + i)
+            .or_else(|| text.find(&line_result.old));
+        if let Some(pos) = pos {
+            text.replace_range(pos..pos + line_result.old.len(), &line_result.new);
+        }
+    }
+
+    if let Some((comment_start, comment_end)) = comment {
+        for (line, rules) in disabled.iter().rev() {
+            let Some(text) = lines.get(line.saturating_sub(1)) else {
+                continue;
+            };
+
+            let indent = text
+                .chars()
+                .take_while(|c| *c == ' ' || *c == '\t')
+                .collect::<String>();
+            let line_ending = if text.ends_with('\r') { "\r" } else { "" };
+            let directive = if rules.is_empty() {
+                "autocorrect-disable-next-line".to_owned()
+            } else {
+                format!("autocorrect-disable-next-line {}", rules.join(", "))
+            };
+            lines.insert(
+                line - 1,
+                format!("{indent}{comment_start}{directive}{comment_end}{line_ending}"),
+            );
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    fn prompt_file(answers: &str, filetype: &str, raw: &str) -> String {
+        let cli = Cli::parse_from(["autocorrect", "--fix", "--interactive"]);
+        let mut session = Session::default();
+        let mut output = vec![];
+        session.prompt_file(
+            &cli,
+            "foo",
+            filetype,
+            raw,
+            &mut answers.as_bytes(),
+            &mut output,
+        )
+    }
+
+    #[test]
+    fn test_prompt_file() {
+        let raw = "// 第1行\nlet a = \"你好hello\";\n    // 测试test\n";
+
+        assert_eq!(raw, prompt_file("n\nn\nn\n", "rust", raw));
+        assert_eq!(
+            raw.replace("第1行", "第 1 行"),
+            prompt_file("y\nn\nn\n", "rust", raw)
+        );
+        // Unknown answer is asked again, and EOF is quit
+        assert_eq!(
+            raw.replace("你好hello", "你好 hello"),
+            prompt_file("n\nx\ny\n", "rust", raw)
+        );
+        // Accept all of the same rules
+        assert_eq!(
+            "// 第 1 行\nlet a = \"你好 hello\";\n    // 测试 test\n",
+            prompt_file("a\n", "rust", raw)
+        );
+
+        let expected = "// 第1行\nlet a = \"你好 hello\";\n    // autocorrect-disable-next-line space-word\n    // 测试test\n";
+        let out = prompt_file("n\ny\nd\n", "rust", raw);
+        assert_eq!(expected, out);
+        // The inserted comment disables the rule
+        assert_eq!(1, autocorrect::lint_for(&out, "rust").lines.len());
+
+        let raw = "# 标题\r\n\r\n你好hello\r\n";
+        assert_eq!(
+            "# 标题\r\n\r\n<!-- autocorrect-disable-next-line space-word -->\r\n你好hello\r\n",
+            prompt_file("d\n", "md", raw)
+        );
+    }
+
+    #[test]
+    fn test_prompt_file_in_multiline_string() {
+        let cases = [
+            ("py", "x = \"\"\"\n你好hello\n\"\"\"\n"),
+            ("js", "const x = `\n你好hello\n`;\n"),
+        ];
+
+        for (filetype, raw) in cases {
+            let cli = Cli::parse_from(["autocorrect", "--fix", "--interactive"]);
+            let mut session = Session::default();
+            let mut output = vec![];
+            // The `d` is not offered, so it is asked again, and EOF is quit
+            let out = session.prompt_file(
+                &cli,
+                "foo",
+                filetype,
+                raw,
+                &mut "d\n".as_bytes(),
+                &mut output,
+            );
+            assert_eq!(raw, out, "{filetype}");
+
+            let output = String::from_utf8(output).unwrap();
+            assert!(!output.contains("[d]isable here"), "{filetype}: {output}");
+            assert_eq!(
+                2,
+                output.matches("Accept this change?").count(),
+                "{filetype}"
+            );
+        }
+
+        // The line where the multi-line string starts can be disabled
+        let raw = "x = \"\"\"你好hello\n世界\n\"\"\"\n";
+        assert_eq!(
+            "# autocorrect-disable-next-line space-word\nx = \"\"\"你好hello\n世界\n\"\"\"\n",
+            prompt_file("d\n", "py", raw)
+        );
+    }
+
+    #[test]
+    fn test_comment_for() {
+        let cases = [
+            ("html", "<p>\n你好hello\n</p>\n"),
+            ("md", "你好hello\n"),
+            ("css", "/* 你好hello */\n"),
+            ("yml", "a: 你好hello\n"),
+            ("rb", "a = \"你好hello\"\n"),
+            ("ex", "a = \"你好hello\"\n"),
+            ("py", "a = \"你好hello\"\n"),
+            ("po", "msgid \"hello\"\nmsgstr \"你好hello\"\n"),
+            ("conf", "# 你好hello\n"),
+            ("sql", "SELECT '你好hello';\n"),
+            ("lua", "a = \"你好hello\"\n"),
+            ("tex", "你好hello\n"),
+            ("rs", "let a = \"你好hello\";\n"),
+            ("go", "a := \"你好hello\"\n"),
+            ("js", "a = \"你好hello\";\n"),
+            ("m", "a = @\"你好hello\";\n"),
+            ("strings", "\"a\" = \"你好hello\";\n"),
+            ("cs", "a = \"你好hello\";\n"),
+            ("swift", "a = \"你好hello\"\n"),
+            ("java", "a = \"你好hello\";\n"),
+            ("scala", "a = \"你好hello\"\n"),
+            ("kt", "a = \"你好hello\"\n"),
+            ("php", "<?php\n// 你好hello\n"),
+            ("dart", "a = \"你好hello\";\n"),
+            ("c", "a = \"你好hello\";\n"),
+            ("zig", "a = \"你好hello\";\n"),
+        ];
+
+        for (filetype, raw) in cases {
+            let comment = comment_for(filetype);
+            assert!(comment.is_some(), "{filetype}");
+
+            let result = autocorrect::lint_for(raw, filetype);
+            assert_eq!(1, result.lines.len(), "{filetype}");
+            let line_result = &result.lines[0];
+            let disabled = BTreeMap::from([(line_result.line, line_result.rules.clone())]);
+            let out = apply(raw, &[], &disabled, comment);
+            assert_eq!(
+                0,
+                autocorrect::lint_for(&out, filetype).lines.len(),
+                "{filetype}: {out}"
+            );
+        }
+
+        assert_eq!(None, comment_for("txt"));
+        assert_eq!(None, comment_for("json"));
+        assert_eq!(None, comment_for("xml"));
+    }
+
+    #[test]
+    fn test_apply() {
+        let line_result = |line, col, old: &str, new: &str| LineResult {
+            line,
+            col,
+            old: old.to_owned(),
+            new: new.to_owned(),
+            ..Default::default()
+        };
+
+        let raw = "a你好b 你好c\n  你好d\n";
+        let first = line_result(1, 1, "a你好b", "a 你好 b");
+        let second = line_result(1, 6, "你好c", "你好 c");
+        let third = line_result(2, 1, "你好d", "你好 d");
+        assert_eq!(
+            "a 你好 b 你好 c\n  你好 d\n",
+            apply(raw, &[&third, &first, &second], &BTreeMap::new(), None)
+        );
+
+        let disabled = BTreeMap::from([(2, vec!["space-word".to_owned()])]);
+        assert_eq!(
+            "a 你好 b 你好c\n  # autocorrect-disable-next-line space-word\n  你好d\n",
+            apply(raw, &[&first], &disabled, Some(("# ", "")))
+        );
+    }
+}
@@ -6,7 +6,7 @@ use clap::Parser;
 use std::ffi::OsString;
 use std::fs;
 use std::io;
-use std::io::BufRead;
+use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -14,9 +14,14 @@ use std::sync::Mutex;
 use std::time::SystemTime;
 
 mod cli;
+mod dictionaries;
+mod i18n;
 mod initializer;
+mod interactive;
 mod logger;
 mod progress;
+mod rules;
+mod stats;
 
 #[cfg(feature = "update")]
 mod update;
@@ -33,12 +38,51 @@ include!(concat!(env!("OUT_DIR"), "/config_template.rs"));
 
 static DEFAULT_CONFIG_FILE: &str = ".autocorrectrc";
 
+/// Exit code when the lint found the issues over `--max-errors` or `--max-warnings`.
+pub const EXIT_ISSUES_FOUND: i32 = 1;
+/// Exit code when a file can't be parsed.
+pub const EXIT_PARSE_ERROR: i32 = 2;
+/// Exit code when the config file can't be loaded.
+pub const EXIT_CONFIG_ERROR: i32 = 3;
+
+/// The counts of the lint results, for the summary and the exit code.
+#[derive(Default, Clone, Copy)]
+struct LintCounts {
+    errors: usize,
+    warnings: usize,
+    parse_errors: usize,
+}
+
+impl LintCounts {
+    fn add(&mut self, other: LintCounts) {
+        self.errors += other.errors;
+        self.warnings += other.warnings;
+        self.parse_errors += other.parse_errors;
+    }
+
+    fn exit_code(&self, cli: &Cli) -> i32 {
+        if self.parse_errors > 0 && !cli.allow_parse_errors {
+            return EXIT_PARSE_ERROR;
+        }
+
+        let max_warnings = cli.max_warnings.or(cli.strict.then_some(0));
+        if self.errors > cli.max_errors || max_warnings.is_some_and(|max| self.warnings > max) {
+            return EXIT_ISSUES_FOUND;
+        }
+
+        0
+    }
+}
+
+/// Run the block and log the elapsed time, returns the elapsed time in ms.
 macro_rules! bench {
-    ($name: expr, $block: block) => {
+    ($name: expr, $block: block) => {{
         let start = SystemTime::now();
         $block;
-        log::debug!("{} {}ms", $name, start.elapsed_millis());
-    };
+        let elapsed = start.elapsed_millis();
+        log::debug!("{} {}ms", $name, elapsed);
+        elapsed
+    }};
     () => {};
 }
 
@@ -48,6 +92,9 @@ where
     T: Into<OsString> + Clone,
 {
     let mut cli = Cli::parse_from(args);
+    if let Err(err) = cli.validate() {
+        err.exit();
+    }
 
     // Set log level
     let log_level = cli.log_level();
@@ -79,58 +126,114 @@ where
             autocorrect_lsp::start().await;
             return;
         }
+        Some(cli::Commands::Dictionaries { ref name }) => {
+            dictionaries::run(name.as_deref());
+            return;
+        }
+        Some(cli::Commands::Rules { format }) => {
+            rules::run(format);
+            return;
+        }
+        Some(cli::Commands::Explain { ref name }) => {
+            rules::explain(name);
+            return;
+        }
+        Some(cli::Commands::I18nCheck {
+            ref source,
+            ref target,
+            format,
+        }) => {
+            load_config(&cli.config_file);
+            i18n::run(&cli, source, target, format);
+            return;
+        }
         _ => {}
     }
 
     load_config(&cli.config_file);
+    if cli.report_unused_disable_directives {
+        autocorrect::config::load("reportUnusedDisableDirectives: true").unwrap();
+    }
 
     let cwd = std::env::current_dir().unwrap();
-    let mut arg_files = cli.files.iter().map(|f| {
-        // For example: autocorrect --lint /Users/jason/project/foo/bar.md
-        //
-        // - f is /Users/jason/project/foo.md
-        // - cwd is /Users/jason/project
-        // - relative_path is foo/bar.md
-        //
-        // Then this will matched with .autocorrectignore rules.
-        //
-        // Convert absolute path to relative path, in cwd
-        if let Ok(relative_path) = Path::new(&f).strip_prefix(&cwd) {
-            relative_path.to_str().unwrap_or("").to_owned()
-        } else {
-            f.to_owned()
-        }
-    });
+    let mut arg_files = cli.files.iter().map(|f| relative_path(f, &cwd));
 
     // calc run time
     let start_t = SystemTime::now();
 
     let mut lint_results: Vec<LintResult> = Vec::new();
-    let lint_errors_count = Arc::new(Mutex::new(0));
-    let lint_warnings_count = Arc::new(Mutex::new(0));
+    let lint_counts = Arc::new(Mutex::new(LintCounts::default()));
+    let lint_stats = Arc::new(Mutex::new(stats::Stats::default()));
 
     if cli.stdin {
-        let mut _err_count = 0;
-        let mut _warn_count = 0;
+        let mut counts = LintCounts::default();
 
-        let raw = read_stdin();
+        let raw = match read_stdin() {
+            Ok(raw) => raw,
+            Err(err) => {
+                log::error!("Failed to read <STDIN> error: {}", err);
+                std::process::exit(1);
+            }
+        };
+
+        // Use the path of `--stdin-filename` for the file type, the ignore rules and the lint output
+        let stdin_filename = cli
+            .stdin_filename
+            .as_deref()
+            .map(|f| relative_path(f, &cwd));
+        let filepath = stdin_filename.as_deref().unwrap_or("<STDIN>");
+        let mut filetype = match stdin_filename {
+            Some(ref filename) => autocorrect::get_file_extension(filename),
+            None => "text".to_owned(),
+        };
+        if let Some(ref ftype) = cli.filetype {
+            filetype = ftype.to_string();
+        }
+
+        let is_ignored = stdin_filename.as_deref().is_some_and(|filename| {
+            autocorrect::ignorer::Ignorer::new(PathBuf::from("./")).is_ignored(filename)
+        });
+        let filetype = filetype.as_str();
 
-        if cli.lint {
-            lint_and_output(
-                "<STDIN>",
-                "text",
+        if is_ignored || !autocorrect::is_support_type(filetype) {
+            // Output as is, for the editors to replace the buffer
+            if !cli.lint && !cli.check_idempotent {
+                write_stdout(&raw);
+            }
+        } else if cli.check_idempotent {
+            check_and_output(
+                filepath,
+                filetype,
                 &raw,
                 &cli,
                 &mut lint_results,
-                &mut _err_count,
-                &mut _warn_count,
+                &mut counts,
             );
+        } else if cli.lint {
+            let elapsed = bench!(format!("Done {filepath}"), {
+                lint_and_output(
+                    filepath,
+                    filetype,
+                    &raw,
+                    &cli,
+                    &mut lint_results,
+                    &mut counts,
+                );
+            });
+            if cli.stats.is_some() {
+                lint_stats.lock().unwrap().add_file(
+                    filepath,
+                    filetype,
+                    &raw,
+                    &lint_results,
+                    elapsed,
+                );
+            }
         } else {
-            format_and_output("", "text", &raw, &cli)
+            format_and_output("", filetype, &raw, &cli, &mut counts)
         }
 
-        *lint_errors_count.lock().unwrap() += _err_count;
-        *lint_warnings_count.lock().unwrap() += _warn_count;
+        lint_counts.lock().unwrap().add(counts);
     } else {
         let (tx, rx) = std::sync::mpsc::channel();
 
@@ -152,6 +255,7 @@ where
 
         // create ignorer for ignore directly file
         let ignorer = autocorrect::ignorer::Ignorer::new(&PathBuf::from("./"));
+        let mut interactive = interactive::Session::default();
 
         for result in walker.build() {
             if let Err(err) = result {
@@ -181,41 +285,65 @@ where
                 continue;
             }
 
+            // Prompt the files one by one, not in the thread pool
+            if cli.fix && cli.interactive {
+                interactive.fix_file(&cli, filepath, &filetype);
+                if interactive.is_quit() {
+                    break;
+                }
+                continue;
+            }
+
             let cli = cli.clone();
             let tx = tx.clone();
-            let lint_errors_count = lint_errors_count.clone();
-            let lint_warnings_count = lint_warnings_count.clone();
+            let lint_counts = lint_counts.clone();
+            let lint_stats = lint_stats.clone();
             let filepath = filepath.to_owned();
             let filetype = filetype.clone();
 
             pool.execute(move || match read_file(&filepath) {
                 Ok(raw) => {
-                    bench!(format!("Done {filepath}"), {
-                        if cli.lint {
-                            let mut lint_results: Vec<LintResult> = Vec::new();
+                    if cli.lint || cli.check_idempotent {
+                        let mut lint_results: Vec<LintResult> = Vec::new();
 
-                            let mut _err_count = 0;
-                            let mut _warn_count = 0;
-                            lint_and_output(
+                        let mut counts = LintCounts::default();
+                        let output = if cli.check_idempotent {
+                            check_and_output
+                        } else {
+                            lint_and_output
+                        };
+                        let elapsed = bench!(format!("Done {filepath}"), {
+                            output(
                                 &filepath,
                                 &filetype,
                                 &raw,
                                 &cli,
                                 &mut lint_results,
-                                &mut _err_count,
-                                &mut _warn_count,
+                                &mut counts,
                             );
+                        });
 
-                            *lint_errors_count.lock().unwrap() += _err_count;
-                            *lint_warnings_count.lock().unwrap() += _warn_count;
+                        lint_counts.lock().unwrap().add(counts);
+                        if cli.stats.is_some() {
+                            lint_stats.lock().unwrap().add_file(
+                                &filepath,
+                                &filetype,
+                                &raw,
+                                &lint_results,
+                                elapsed,
+                            );
+                        }
 
-                            for lint_result in lint_results {
-                                tx.send(lint_result).unwrap();
-                            }
-                        } else {
-                            format_and_output(&filepath, &filetype, &raw, &cli);
+                        for lint_result in lint_results {
+                            tx.send(lint_result).unwrap();
                         }
-                    });
+                    } else {
+                        let mut counts = LintCounts::default();
+                        bench!(format!("Done {filepath}"), {
+                            format_and_output(&filepath, &filetype, &raw, &cli, &mut counts);
+                        });
+                        lint_counts.lock().unwrap().add(counts);
+                    }
                 }
                 Err(err) => {
                     log::error!("Failed to read: {} error: {}", filepath, err);
@@ -233,49 +361,114 @@ where
 
     log::debug!("Lint result found: {} issues.", lint_results.len());
 
-    if !cli.stdin && !cli.quiet {
+    // end the line of the progress dots
+    if cli.show_progress() {
         log::info!("");
     }
 
-    if cli.lint {
-        if cli.formatter.is_diff() {
-            let _err_count = *lint_errors_count.lock().unwrap();
-            let _warn_count = *lint_warnings_count.lock().unwrap();
-
+    let counts = *lint_counts.lock().unwrap();
+    if let Some(format) = cli.stats.filter(|_| cli.lint) {
+        let elapsed = start_t.elapsed_millis();
+        write_report(&cli, &lint_stats.lock().unwrap().output(format, elapsed));
+
+        exit_with(counts.exit_code(&cli));
+    } else if cli.lint || cli.check_idempotent {
+        if cli.formatter == cli::OutputFormatter::Patch {
+            // The patch is printed by each file, the files are not idempotent are not in it
+            exit_with(counts.exit_code(&cli));
+        } else if cli.formatter.is_diff() {
             for lint_result in &lint_results {
                 log::info!("{}", lint_result.to_diff(cli.no_diff_bg_color))
             }
 
-            if _err_count > 0 || _warn_count > 0 {
+            if counts.errors > 0 || counts.warnings > 0 {
                 log::info!(
                     "{}, {}",
-                    format!("Error: {_err_count}").red(),
-                    format!("Warning: {_warn_count}").yellow(),
+                    format!("Error: {}", counts.errors).red(),
+                    format!("Warning: {}", counts.warnings).yellow(),
                 );
-            } else {
+            } else if counts.parse_errors == 0 {
                 log::info!("No issues found.");
             }
 
+            if counts.parse_errors > 0 {
+                log::info!("{}", format!("Parse error: {}", counts.parse_errors).red());
+            }
+
             progress::finish(&cli, start_t);
 
-            if _err_count > 0 {
-                // Exit with code = 1
-                std::process::exit(1);
-            }
-            if cli.strict && _warn_count > 0 {
-                // Exit with code = 1
-                std::process::exit(1);
-            }
-        } else if cli.formatter == cli::OutputFormatter::Json {
-            log::info!("{}", autocorrect::json::to_lint_results_json(lint_results));
+            exit_with(counts.exit_code(&cli));
         } else {
-            log::info!(
-                "{}",
-                autocorrect::rdjson::to_lint_results_rdjson(lint_results)
-            )
+            let report = match cli.formatter {
+                cli::OutputFormatter::Json => autocorrect::json::to_lint_results_json(lint_results),
+                cli::OutputFormatter::Gitlab => {
+                    autocorrect::gitlab::to_lint_results_gitlab(lint_results)
+                }
+                cli::OutputFormatter::Checkstyle => {
+                    autocorrect::checkstyle::to_lint_results_checkstyle(lint_results)
+                }
+                cli::OutputFormatter::Junit => {
+                    autocorrect::junit::to_lint_results_junit(lint_results)
+                }
+                cli::OutputFormatter::Html => autocorrect::html::to_lint_results_html(lint_results),
+                cli::OutputFormatter::Github => {
+                    write_github_summary(&lint_results);
+                    autocorrect::github::to_lint_results_github(lint_results)
+                }
+                _ => autocorrect::rdjson::to_lint_results_rdjson(lint_results),
+            };
+            write_report(&cli, &report);
+
+            exit_with(counts.exit_code(&cli));
         }
     } else if cli.fix {
         progress::finish(&cli, start_t);
+
+        // Keep the stdout only the output of the stdin
+        if counts.parse_errors > 0 && !cli.stdin {
+            log::info!("{}", format!("Parse error: {}", counts.parse_errors).red());
+        }
+        exit_with(counts.exit_code(&cli));
+    }
+}
+
+/// Exit with the code, unless it is 0.
+fn exit_with(code: i32) {
+    if code != 0 {
+        std::process::exit(code);
+    }
+}
+
+/// Write the lint report to the file of `--output`, or stdout.
+pub(crate) fn write_report(cli: &Cli, report: &str) {
+    match cli.output {
+        Some(ref output) => {
+            if let Err(err) = fs::write(output, report) {
+                log::error!("Failed to write: {} error: {}", output, err);
+                std::process::exit(1);
+            }
+        }
+        None => log::info!("{}", report),
+    }
+}
+
+/// Append the job summary to the file of `GITHUB_STEP_SUMMARY`, when running in GitHub Actions.
+pub(crate) fn write_github_summary(lint_results: &[LintResult]) {
+    let Ok(summary_path) = std::env::var("GITHUB_STEP_SUMMARY") else {
+        return;
+    };
+    if summary_path.is_empty() {
+        return;
+    }
+
+    let summary = autocorrect::github::to_lint_results_github_summary(lint_results);
+    let written = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&summary_path)
+        .and_then(|mut file| io::Write::write_all(&mut file, summary.as_bytes()));
+    if let Err(err) = written {
+        log::error!("Failed to write GITHUB_STEP_SUMMARY {summary_path}: {err}");
     }
 }
 
@@ -290,32 +483,68 @@ fn read_file(filepath: &str) -> io::Result<String> {
     out
 }
 
-/// Read stdin into a string
+/// Read stdin into a string, keep the line endings and the newline at end as is.
 #[inline]
-fn read_stdin() -> String {
-    io::stdin()
-        .lock()
-        .lines()
-        .collect::<Result<Vec<_>, _>>()
-        .unwrap()
-        .join("\n")
+fn read_stdin() -> io::Result<String> {
+    io::read_to_string(io::stdin().lock())
+}
+
+/// Write the text to stdout exactly, without the newline at end.
+fn write_stdout(text: &str) {
+    let mut stdout = io::stdout().lock();
+    let written = stdout
+        .write_all(text.as_bytes())
+        .and_then(|_| stdout.flush());
+    if let Err(err) = written {
+        log::error!("Failed to write <STDOUT> error: {}", err);
+    }
+}
+
+/// Convert the absolute path to the relative path in cwd, for matching the `.autocorrectignore` rules.
+///
+/// For example: autocorrect --lint /Users/jason/project/foo/bar.md
+///
+/// - f is /Users/jason/project/foo/bar.md
+/// - cwd is /Users/jason/project
+/// - relative_path is foo/bar.md
+fn relative_path(f: &str, cwd: &Path) -> String {
+    if let Ok(relative_path) = Path::new(f).strip_prefix(cwd) {
+        relative_path.to_str().unwrap_or("").to_owned()
+    } else {
+        f.to_owned()
+    }
 }
 
 #[inline]
 pub fn load_config(filename: &str) {
     log::debug!("Load config: {}", filename);
 
-    autocorrect::config::load_file(&filename).unwrap_or_else(|e| {
-        panic!("Load config file: {}\nerror: {}", filename, e);
-    });
+    if let Err(e) = autocorrect::config::load_file(&filename) {
+        log::error!("Load config file: {}\nerror: {}", filename, e);
+        std::process::exit(EXIT_CONFIG_ERROR);
+    }
 }
 
-fn format_and_output(filepath: &str, filetype: &str, raw: &str, cli: &Cli) {
+fn format_and_output(
+    filepath: &str,
+    filetype: &str,
+    raw: &str,
+    cli: &Cli,
+    counts: &mut LintCounts,
+) {
     let result = autocorrect::format_for(raw, filetype);
+    if result.has_error() {
+        counts.parse_errors += 1;
+    }
 
     if cli.fix && !filepath.is_empty() {
         if result.has_error() {
-            log::debug!("{}\n{}", filepath, result.error);
+            // Lint again for the position of the parse error
+            let (line, col) = autocorrect::lint_for(raw, filetype).error_position();
+            progress::err(cli);
+            // Start a new line after the progress dots
+            let newline = if cli.show_progress() { "\n" } else { "" };
+            log::error!("{newline}{}:{}:{} {}", filepath, line, col, result.error);
             return;
         }
 
@@ -323,11 +552,27 @@ fn format_and_output(filepath: &str, filetype: &str, raw: &str, cli: &Cli) {
         if !filepath.is_empty() {
             if result.out.eq(&String::from(raw)) {
                 progress::ok(cli);
+            } else if cli.dry_run {
+                if cli.formatter == cli::OutputFormatter::Patch {
+                    print!(
+                        "{}",
+                        autocorrect::patch::to_patch(filepath, raw, &result.out)
+                    );
+                } else {
+                    log::info!("Would fix: {}", filepath);
+                }
             } else {
                 progress::err(cli);
                 fs::write(Path::new(filepath), result.out).unwrap();
             }
         }
+    } else if cli.stdin {
+        if result.has_error() {
+            log::debug!("<STDIN>\n{}", result.error);
+        }
+
+        // The out is the raw when has error, keep the bytes as is for the pipe
+        write_stdout(&result.out);
     } else {
         if result.has_error() {
             log::error!("{}", raw);
@@ -345,14 +590,19 @@ fn lint_and_output(
     raw: &str,
     cli: &Cli,
     results: &mut Vec<LintResult>,
-    errors_count: &mut usize,
-    warrings_count: &mut usize,
+    counts: &mut LintCounts,
 ) {
     let mut result = autocorrect::lint_for(raw, filetype);
     result.filepath = String::from(filepath);
 
-    *errors_count += result.errors_count();
-    *warrings_count += result.warnings_count();
+    counts.errors += result.errors_count();
+    counts.warnings += result.warnings_count();
+    if result.has_error() {
+        counts.parse_errors += 1;
+        progress::err(cli);
+        results.push(result);
+        return;
+    }
 
     // do not print anything, when not lint results
     if result.lines.is_empty() {
@@ -360,16 +610,121 @@ fn lint_and_output(
         return;
     }
 
-    if *errors_count > 0 {
+    if counts.errors > 0 {
         progress::err(cli);
-    } else if *warrings_count > 0 {
+    } else if counts.warnings > 0 {
         progress::warn(cli);
     }
 
-    if cli.formatter.is_diff() && result.has_error() {
-        log::debug!("{}\n{}", filepath, result.error);
+    results.push(result);
+}
+
+/// Format twice and output the lines changed by the second format, for `--check-idempotent`.
+fn check_and_output(
+    filepath: &str,
+    filetype: &str,
+    raw: &str,
+    cli: &Cli,
+    results: &mut Vec<LintResult>,
+    counts: &mut LintCounts,
+) {
+    let checked = autocorrect::format_for_checked(raw, filetype, cli.max_iterations);
+    if checked.result.has_error() {
+        // Lint again for the structured parse error
+        let mut result = autocorrect::lint_for(raw, filetype);
+        result.filepath = String::from(filepath);
+        if !result.has_error() {
+            result.error = checked.result.error;
+        }
+
+        counts.parse_errors += 1;
+        progress::err(cli);
+        results.push(result);
+        return;
+    }
+
+    // Only write the fixed point, the output is not changed by the format again
+    let fixed = checked.converged && checked.error.is_empty();
+    if cli.fix && !cli.stdin && fixed && checked.out != raw {
+        if !cli.dry_run {
+            fs::write(Path::new(filepath), &checked.out).unwrap();
+        } else if cli.formatter == cli::OutputFormatter::Patch {
+            print!(
+                "{}",
+                autocorrect::patch::to_patch(filepath, raw, &checked.out)
+            );
+        }
+    }
+
+    if checked.is_idempotent() {
+        progress::ok(cli);
         return;
     }
+    progress::err(cli);
+
+    let result = checked.to_lint_result(filepath);
+    counts.errors += result.errors_count();
+    if result.has_error() {
+        // The output of the first format can't be parsed again
+        counts.errors += 1;
+        log::error!(
+            "{}: the formatted output can't be parsed again\n{}",
+            filepath,
+            result.error
+        );
+    }
+
+    results.push(result);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exit_code(args: &[&str], errors: usize, warnings: usize, parse_errors: usize) -> i32 {
+        let cli = Cli::parse_from([&["autocorrect", "--lint"], args].concat());
+        LintCounts {
+            errors,
+            warnings,
+            parse_errors,
+        }
+        .exit_code(&cli)
+    }
+
+    #[test]
+    fn test_exit_code() {
+        assert_eq!(0, exit_code(&[], 0, 3, 0));
+        assert_eq!(EXIT_ISSUES_FOUND, exit_code(&[], 1, 0, 0));
+        assert_eq!(EXIT_ISSUES_FOUND, exit_code(&["--strict"], 0, 1, 0));
+        assert_eq!(EXIT_PARSE_ERROR, exit_code(&[], 1, 0, 1));
+        assert_eq!(0, exit_code(&["--allow-parse-errors"], 0, 0, 1));
+        assert_eq!(
+            EXIT_ISSUES_FOUND,
+            exit_code(&["--allow-parse-errors"], 1, 0, 1)
+        );
+
+        assert_eq!(0, exit_code(&["--max-errors", "2"], 2, 0, 0));
+        assert_eq!(
+            EXIT_ISSUES_FOUND,
+            exit_code(&["--max-errors", "2"], 3, 0, 0)
+        );
+        assert_eq!(0, exit_code(&["--max-warnings", "2"], 0, 2, 0));
+        assert_eq!(
+            EXIT_ISSUES_FOUND,
+            exit_code(&["--max-warnings", "2"], 0, 3, 0)
+        );
+        // --max-warnings overrides --strict
+        assert_eq!(0, exit_code(&["--strict", "--max-warnings", "5"], 0, 3, 0));
+    }
 
-    results.push(result.clone());
+    #[test]
+    fn test_relative_path() {
+        let cwd = Path::new("/home/jason/project");
+        assert_eq!(
+            "foo/bar.md",
+            relative_path("/home/jason/project/foo/bar.md", cwd)
+        );
+        assert_eq!("foo/bar.md", relative_path("foo/bar.md", cwd));
+        assert_eq!("/tmp/bar.md", relative_path("/tmp/bar.md", cwd));
+    }
 }
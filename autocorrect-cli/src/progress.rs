@@ -4,7 +4,7 @@ use std::time::SystemTime;
 use crate::{cli::Cli, logger::SystemTimeDuration as _};
 
 pub fn ok(cli: &Cli) {
-    if cli.quiet || !cli.formatter.is_diff() {
+    if !cli.show_progress() {
         return;
     }
 
@@ -12,7 +12,7 @@ pub fn ok(cli: &Cli) {
 }
 
 pub fn warn(cli: &Cli) {
-    if cli.quiet || !cli.formatter.is_diff() {
+    if !cli.show_progress() {
         return;
     }
 
@@ -20,7 +20,7 @@ pub fn warn(cli: &Cli) {
 }
 
 pub fn err(cli: &Cli) {
-    if cli.quiet || !cli.formatter.is_diff() {
+    if !cli.show_progress() {
         return;
     }
 
@@ -29,7 +29,7 @@ pub fn err(cli: &Cli) {
 
 /// print time spend from start_t to now
 pub fn finish(cli: &Cli, start_t: SystemTime) {
-    if cli.quiet || !cli.formatter.is_diff() {
+    if !cli.show_progress() {
         return;
     }
 
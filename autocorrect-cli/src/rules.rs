@@ -0,0 +1,93 @@
+use autocorrect::config::SeverityMode;
+use autocorrect::{Phase, RuleInfo};
+use owo_colors::OwoColorize;
+
+use crate::cli::RulesFormatter;
+
+fn severity_name(severity: SeverityMode) -> &'static str {
+    match severity {
+        SeverityMode::Off => "off",
+        SeverityMode::Error => "error",
+        SeverityMode::Warning => "warning",
+    }
+}
+
+fn phase_name(phase: Phase) -> &'static str {
+    match phase {
+        Phase::Part => "part",
+        Phase::After => "after",
+    }
+}
+
+/// List the rules with the description and default severity.
+pub(crate) fn run(format: RulesFormatter) {
+    let rules = autocorrect::rules();
+
+    if format == RulesFormatter::Json {
+        println!("{}", serde_json::to_string_pretty(&rules).unwrap());
+        return;
+    }
+
+    log::info!("Rules:\n");
+    for rule in rules {
+        log::info!(
+            "  {:<26}{:<9}{}",
+            rule.name.green(),
+            severity_name(rule.default_severity),
+            rule.description
+        );
+    }
+    log::info!("\nRun `autocorrect explain <rule>` to see the examples of a rule.");
+}
+
+/// Show the metadata of a rule, and run the examples through the formatter with the rule enabled.
+pub(crate) fn explain(name: &str) {
+    let Some(rule) = autocorrect::rule_info(name) else {
+        log::error!("Unknown rule: {}", name);
+        log::info!("Run `autocorrect rules` to list the rules.");
+        std::process::exit(1);
+    };
+
+    log::info!("{}\n", rule.name.green());
+    log::info!("{}\n", rule.description);
+    log::info!("Default severity: {}", severity_name(rule.default_severity));
+    log::info!("Phase: {}", phase_name(rule.phase));
+    if let Some(context) = rule.context {
+        log::info!("Context: {}", context);
+    }
+
+    if rule.examples.is_empty() {
+        return;
+    }
+
+    if let Err(err) = load_example_config(&rule) {
+        log::error!("Failed to load the example config: {}", err);
+        std::process::exit(1);
+    }
+
+    log::info!("\nExamples:\n");
+    // The context rules are only applied in the document, e.g.: the heading of Markdown.
+    let filetype = if rule.context.is_some() {
+        "markdown"
+    } else {
+        "text"
+    };
+    for example in rule.examples.iter() {
+        let result = autocorrect::format_for(&example.bad, filetype);
+        log::info!("  {} {}", "-".red(), example.bad.red());
+        log::info!("  {} {}\n", "+".green(), result.out.green());
+    }
+
+    if let Some(config) = rule.example_config {
+        log::info!("With the config:\n\n{}", config);
+    }
+}
+
+fn load_example_config(rule: &RuleInfo) -> Result<(), autocorrect::config::Error> {
+    if let Some(config) = &rule.example_config {
+        autocorrect::config::load(config)?;
+    }
+    autocorrect::config::load(&format!("rules:\n  {}: 1\n", rule.name))?;
+
+    Ok(())
+}
@@ -0,0 +1,378 @@
+use std::collections::{BTreeMap, HashMap};
+
+use autocorrect::LintResult;
+use owo_colors::OwoColorize;
+use serde_json::json;
+
+use crate::cli::RulesFormatter;
+
+/// The number of the top offending files to show.
+const TOP_FILES: usize = 10;
+
+#[derive(Default, Clone)]
+struct Count {
+    files: usize,
+    errors: usize,
+    warnings: usize,
+    /// The elapsed time in ms, only for the file types.
+    elapsed: f64,
+}
+
+impl Count {
+    fn to_json(&self) -> serde_json::Value {
+        json!({
+            "files": self.files,
+            "errors": self.errors,
+            "warnings": self.warnings,
+        })
+    }
+}
+
+/// The lint statistics for `--stats`, aggregate the results by rule, file type and directory.
+#[derive(Default)]
+pub(crate) struct Stats {
+    files: usize,
+    cjk_chars: usize,
+    errors: usize,
+    warnings: usize,
+    parse_errors: usize,
+    by_rule: BTreeMap<String, Count>,
+    by_filetype: BTreeMap<String, Count>,
+    by_dir: BTreeMap<String, Count>,
+    by_file: HashMap<String, Count>,
+}
+
+fn dirname(filepath: &str) -> &str {
+    match filepath.rfind('/') {
+        Some(i) => &filepath[..i],
+        None => ".",
+    }
+}
+
+impl Stats {
+    /// Add a processed file, the `results` only has the file has problems.
+    pub fn add_file(
+        &mut self,
+        filepath: &str,
+        filetype: &str,
+        raw: &str,
+        results: &[LintResult],
+        elapsed: f64,
+    ) {
+        let filepath = filepath.trim_start_matches("./");
+        let mut count = Count {
+            files: 1,
+            ..Default::default()
+        };
+
+        for result in results {
+            count.errors += result.errors_count();
+            count.warnings += result.warnings_count();
+            if result.has_error() {
+                self.parse_errors += 1;
+            }
+
+            for line in result.lines.iter() {
+                let rules = if line.rules.is_empty() {
+                    vec!["other".to_owned()]
+                } else {
+                    line.rules.clone()
+                };
+
+                for rule in rules {
+                    let rule_count = self.by_rule.entry(rule).or_default();
+                    if line.severity.is_error() {
+                        rule_count.errors += 1;
+                    } else if line.severity.is_warning() {
+                        rule_count.warnings += 1;
+                    }
+                }
+            }
+        }
+
+        self.files += 1;
+        self.cjk_chars += autocorrect::cjk_count(raw);
+        self.errors += count.errors;
+        self.warnings += count.warnings;
+
+        let filetype = self
+            .by_filetype
+            .entry(autocorrect::match_filename(filetype))
+            .or_default();
+        filetype.files += 1;
+        filetype.errors += count.errors;
+        filetype.warnings += count.warnings;
+        filetype.elapsed += elapsed;
+
+        let dir = self.by_dir.entry(dirname(filepath).to_owned()).or_default();
+        dir.files += 1;
+        dir.errors += count.errors;
+        dir.warnings += count.warnings;
+
+        if count.errors + count.warnings > 0 {
+            self.by_file.insert(filepath.to_owned(), count);
+        }
+    }
+
+    /// The files have the most problems, sorted by the count and the path.
+    fn top_files(&self) -> Vec<(&String, &Count)> {
+        let mut files = self.by_file.iter().collect::<Vec<_>>();
+        files.sort_by(|(a_path, a), (b_path, b)| {
+            (b.errors + b.warnings)
+                .cmp(&(a.errors + a.warnings))
+                .then(a_path.cmp(b_path))
+        });
+        files.truncate(TOP_FILES);
+        files
+    }
+
+    fn to_json(&self, elapsed: f64) -> String {
+        let by_count = |counts: &BTreeMap<String, Count>| {
+            counts
+                .iter()
+                .map(|(name, count)| (name.clone(), count.to_json()))
+                .collect::<serde_json::Map<_, _>>()
+        };
+
+        let by_rule = self
+            .by_rule
+            .iter()
+            .map(|(name, count)| {
+                (
+                    name.clone(),
+                    json!({ "errors": count.errors, "warnings": count.warnings }),
+                )
+            })
+            .collect::<serde_json::Map<_, _>>();
+        let by_filetype = self
+            .by_filetype
+            .iter()
+            .map(|(name, count)| {
+                let mut value = count.to_json();
+                value["elapsed_ms"] = json!(round_ms(count.elapsed));
+                (name.clone(), value)
+            })
+            .collect::<serde_json::Map<_, _>>();
+        let top_files = self
+            .top_files()
+            .into_iter()
+            .map(|(path, count)| {
+                json!({ "path": path, "errors": count.errors, "warnings": count.warnings })
+            })
+            .collect::<Vec<_>>();
+
+        serde_json::to_string_pretty(&json!({
+            "files": self.files,
+            "cjk_chars": self.cjk_chars,
+            "errors": self.errors,
+            "warnings": self.warnings,
+            "parse_errors": self.parse_errors,
+            "elapsed_ms": round_ms(elapsed),
+            "by_rule": by_rule,
+            "by_filetype": by_filetype,
+            "by_dir": by_count(&self.by_dir),
+            "top_files": top_files,
+        }))
+        .unwrap()
+    }
+
+    fn to_table(&self, elapsed: f64) -> String {
+        let mut out = format!(
+            "Files: {}, CJK chars: {}, {}, {}, ",
+            self.files,
+            self.cjk_chars,
+            format!("Error: {}", self.errors).red(),
+            format!("Warning: {}", self.warnings).yellow(),
+        );
+        if self.parse_errors > 0 {
+            out.push_str(&format!(
+                "{}, ",
+                format!("Parse error: {}", self.parse_errors).red()
+            ));
+        }
+        out.push_str(&format!("Time: {}ms\n", round_ms(elapsed)));
+
+        out.push_str(&table(
+            &["Rule", "Errors", "Warnings"],
+            self.by_rule
+                .iter()
+                .map(|(name, count)| {
+                    vec![
+                        name.clone(),
+                        count.errors.to_string(),
+                        count.warnings.to_string(),
+                    ]
+                })
+                .collect(),
+        ));
+        out.push_str(&table(
+            &["File type", "Files", "Errors", "Warnings", "Time (ms)"],
+            self.by_filetype
+                .iter()
+                .map(|(name, count)| {
+                    vec![
+                        name.clone(),
+                        count.files.to_string(),
+                        count.errors.to_string(),
+                        count.warnings.to_string(),
+                        round_ms(count.elapsed).to_string(),
+                    ]
+                })
+                .collect(),
+        ));
+        out.push_str(&table(
+            &["Directory", "Files", "Errors", "Warnings"],
+            self.by_dir
+                .iter()
+                .map(|(name, count)| {
+                    vec![
+                        name.clone(),
+                        count.files.to_string(),
+                        count.errors.to_string(),
+                        count.warnings.to_string(),
+                    ]
+                })
+                .collect(),
+        ));
+        out.push_str(&table(
+            &["Top files", "Errors", "Warnings"],
+            self.top_files()
+                .into_iter()
+                .map(|(path, count)| {
+                    vec![
+                        path.clone(),
+                        count.errors.to_string(),
+                        count.warnings.to_string(),
+                    ]
+                })
+                .collect(),
+        ));
+
+        out
+    }
+
+    /// Output the stats in the format.
+    pub fn output(&self, format: RulesFormatter, elapsed: f64) -> String {
+        match format {
+            RulesFormatter::Json => self.to_json(elapsed),
+            RulesFormatter::Text => self.to_table(elapsed),
+        }
+    }
+}
+
+fn round_ms(ms: f64) -> f64 {
+    (ms * 100.0).round() / 100.0
+}
+
+/// Render a table with the columns aligned, the first column is left aligned and the others are right aligned.
+fn table(headers: &[&str], rows: Vec<Vec<String>>) -> String {
+    if rows.is_empty() {
+        return String::new();
+    }
+
+    let mut widths = headers.iter().map(|h| h.len()).collect::<Vec<_>>();
+    for row in rows.iter() {
+        for (i, cell) in row.iter().enumerate() {
+            widths[i] = widths[i].max(cell.chars().count());
+        }
+    }
+
+    let render = |cells: Vec<&str>| {
+        let line = cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| {
+                let pad = " ".repeat(widths[i] - cell.chars().count());
+                if i == 0 {
+                    format!("{cell}{pad}")
+                } else {
+                    format!("{pad}{cell}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+        format!("{}\n", line.trim_end())
+    };
+
+    let mut out = String::from("\n");
+    out.push_str(&render(headers.to_vec()));
+    for row in rows.iter() {
+        out.push_str(&render(row.iter().map(|c| c.as_str()).collect()));
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use autocorrect::{LineResult, Severity};
+
+    fn lint_result(filepath: &str, lines: &[(&str, Severity)]) -> LintResult {
+        let mut result = LintResult::new("");
+        result.filepath = filepath.to_owned();
+        for (rule, severity) in lines {
+            result.lines.push(LineResult {
+                severity: *severity,
+                rules: vec![rule.to_string()],
+                ..Default::default()
+            });
+        }
+        result
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut stats = Stats::default();
+        let results = [lint_result(
+            "./docs/a.md",
+            &[
+                ("space-word", Severity::Error),
+                ("fullwidth", Severity::Warning),
+            ],
+        )];
+        stats.add_file("./docs/a.md", "md", "你好hello，世界.", &results, 1.5);
+        let results = [lint_result("b.md", &[("space-word", Severity::Error)])];
+        stats.add_file("b.md", "md", "中文abc", &results, 0.5);
+        stats.add_file("docs/c.txt", "txt", "Hello", &[], 0.25);
+        let mut result = lint_result("d.vue", &[]);
+        result.error = "html parse error: expected tag".to_owned();
+        stats.add_file("d.vue", "vue", "<div", &[result], 0.25);
+
+        assert_eq!(
+            (4, 6, 2, 1, 1),
+            (
+                stats.files,
+                stats.cjk_chars,
+                stats.errors,
+                stats.warnings,
+                stats.parse_errors
+            )
+        );
+        assert_eq!(2, stats.by_rule["space-word"].errors);
+        assert_eq!(1, stats.by_rule["fullwidth"].warnings);
+        assert_eq!(2, stats.by_filetype["markdown"].files);
+        assert_eq!(2.0, stats.by_filetype["markdown"].elapsed);
+        assert_eq!(2, stats.by_dir["docs"].files);
+        assert_eq!(
+            vec!["docs/a.md", "b.md"],
+            stats
+                .top_files()
+                .into_iter()
+                .map(|(path, _)| path.as_str())
+                .collect::<Vec<_>>()
+        );
+
+        let expected = "\nRule        Errors  Warnings\nfullwidth        0         1\nspace-word       2         0\n";
+        assert_eq!(
+            expected,
+            table(
+                &["Rule", "Errors", "Warnings"],
+                vec![
+                    vec!["fullwidth".into(), "0".into(), "1".into()],
+                    vec!["space-word".into(), "2".into(), "0".into()],
+                ]
+            )
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+/// Create an empty directory for the files of a test, the config of the repo is not loaded in it.
+fn workdir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("autocorrect-cli-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn autocorrect(dir: &PathBuf, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_autocorrect"))
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn autocorrect_stdin(dir: &PathBuf, args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_autocorrect"))
+        .current_dir(dir)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn test_stdin_output_is_byte_exact() {
+    let dir = workdir("stdin");
+    let raw = "# 标题test\r\n\r\n你好hello\r\nworld";
+
+    for args in [
+        &["--stdin"][..],
+        &["--stdin", "--fix"],
+        &["--stdin", "--fix", "--stdin-filename", "foo.md"],
+    ] {
+        let output = autocorrect_stdin(&dir, args, raw);
+        assert_eq!(Some(0), output.status.code(), "{args:?}");
+        assert_eq!(
+            "# 标题 test\r\n\r\n你好 hello\r\nworld",
+            String::from_utf8(output.stdout).unwrap(),
+            "{args:?}"
+        );
+    }
+
+    // The ignored file is output as is
+    fs::write(dir.join(".autocorrectignore"), "foo.md\n").unwrap();
+    let output = autocorrect_stdin(
+        &dir,
+        &["--stdin", "--fix", "--stdin-filename", "foo.md"],
+        raw,
+    );
+    assert_eq!(raw, String::from_utf8(output.stdout).unwrap());
+
+    // No progress dots and spend time in the lint output
+    let output = autocorrect_stdin(&dir, &["--stdin", "--lint"], "你好 hello\n");
+    assert_eq!(
+        "No issues found.\n",
+        String::from_utf8(output.stdout).unwrap()
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_fix_with_parse_error() {
+    let dir = workdir("fix-parse-error");
+    let raw = "<?php #[ ?>\n";
+    fs::write(dir.join("foo.php"), raw).unwrap();
+    fs::write(dir.join("bar.md"), "你好hello\n").unwrap();
+
+    let output = autocorrect(&dir, &["--fix", "."]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(Some(2), output.status.code(), "{stdout}");
+    assert!(stdout.contains("foo.php:2:1 php parse error"), "{stdout}");
+    assert!(stdout.contains("Parse error: 1"), "{stdout}");
+    // The file can't be parsed is not changed, the others are still fixed
+    assert_eq!(raw, fs::read_to_string(dir.join("foo.php")).unwrap());
+    assert_eq!(
+        "你好 hello\n",
+        fs::read_to_string(dir.join("bar.md")).unwrap()
+    );
+
+    let output = autocorrect(&dir, &["--fix", "--allow-parse-errors", "."]);
+    assert_eq!(Some(0), output.status.code());
+
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -114,7 +114,18 @@ impl Backend {
                     },
                     source,
                     severity,
-                    message: result.new.clone(),
+                    // The rule name for the hover, e.g.: `space-word`
+                    code: result
+                        .rules
+                        .first()
+                        .map(|name| NumberOrString::String(name.clone())),
+                    message: if result.message.is_empty() {
+                        result.new.clone()
+                    } else {
+                        result.message.clone()
+                    },
+                    // The suggestion for code action, because message may not be the new text.
+                    data: Some(serde_json::json!([result.new])),
                     ..Default::default()
                 }
             })
@@ -334,6 +345,7 @@ impl LanguageServer for Backend {
                     },
                 )),
                 document_formatting_provider: Some(OneOf::Left(false)),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
 
                 code_action_provider: Some(CodeActionProviderCapability::Options(
                     CodeActionOptions {
@@ -471,6 +483,44 @@ impl LanguageServer for Backend {
         Ok(None)
     }
 
+    async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
+        let TextDocumentPositionParams {
+            text_document,
+            position,
+        } = params.text_document_position_params;
+
+        let diagnostics = self.diagnostics.read().unwrap();
+        let Some(diagnostics) = diagnostics.get(&text_document.uri) else {
+            return Ok(None);
+        };
+
+        let mut contents = vec![];
+        for diagnostic in diagnostics.iter() {
+            if position < diagnostic.range.start || position > diagnostic.range.end {
+                continue;
+            }
+
+            let Some(NumberOrString::String(name)) = &diagnostic.code else {
+                continue;
+            };
+            if let Some(rule) = autocorrect::rule_info(name) {
+                contents.push(rule_hover(&rule));
+            }
+        }
+
+        if contents.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: contents.join("\n\n---\n\n"),
+            }),
+            range: None,
+        }))
+    }
+
     async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
         let CodeActionParams {
             text_document,
@@ -555,6 +605,19 @@ impl LanguageServer for Backend {
     }
 }
 
+/// The hover content of a rule, with the description and examples.
+fn rule_hover(rule: &autocorrect::RuleInfo) -> String {
+    let mut value = format!("**{}**\n\n{}", rule.name, rule.description);
+    for example in rule.examples.iter() {
+        value.push_str(&format!(
+            "\n\n```diff\n- {}\n+ {}\n```",
+            example.bad, example.good
+        ));
+    }
+
+    value
+}
+
 pub async fn start() {
     let stdin = tokio::io::stdin();
     let stdout = tokio::io::stdout();
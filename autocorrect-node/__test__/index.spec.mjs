@@ -1,6 +1,6 @@
 import test from 'ava';
 
-import { format, formatFor, Ignorer, lintFor, loadConfig } from '../index.js';
+import { format, formatFor, Ignorer, lintFor, loadConfig, registerRule, unregisterRule } from '../index.js';
 
 test('format', (t) => {
   t.assert(format('Hello你好.') === 'Hello 你好。');
@@ -22,6 +22,7 @@ test('lintFor', (t) => {
         new: 'Hello 你好。',
         old: 'Hello你好.',
         severity: 1,
+        message: '',
       },
     ],
   });
@@ -40,3 +41,25 @@ test('Ignorer', (t) => {
   t.assert(ignorer.isIgnored('Cagro.toml') === false);
   t.assert(ignorer.isIgnored('__test__/foo/bar.js') === false);
 });
+
+test('registerRule', (t) => {
+  registerRule('no-ellipsis', (text) => text.replace('...', '……'), {
+    severity: 2,
+    message: 'Use `……` for ellipsis',
+  });
+
+  t.assert(format('你好...') === '你好...');
+  t.deepEqual(lintFor('你好...', 'text').lines, [
+    {
+      c: 1,
+      l: 1,
+      new: '你好……',
+      old: '你好...',
+      severity: 2,
+      message: 'Use `……` for ellipsis',
+    },
+  ]);
+
+  t.assert(unregisterRule('no-ellipsis'));
+  t.deepEqual(lintFor('你好...', 'text').lines, []);
+});
@@ -9,6 +9,7 @@ export interface LineResult {
   new: string
   old: string
   severity: number
+  message: string
 }
 export interface LintResult {
   filepath: string
@@ -18,7 +19,21 @@ export interface LintResult {
 export function format(text: string): string
 export function formatFor(text: string, filepath: string): string
 export function lintFor(text: string, filepath: string): LintResult
+/** Lint a file content and output the report in the `format`, e.g.: `json`, `rdjson`, `checkstyle`, `junit`. */
+export function lintReportFor(text: string, filepath: string, format: string): string
 export function loadConfig(configStr: string): void
+export interface RuleOptions {
+  /** `after` (default) to format the whole line, or `part` to format each part of the line has CJK. */
+  phase?: string
+  /** The severity when the rule is not in the `rules` config, 1: error (default), 2: warning. */
+  severity?: number
+  /** The message for the lint results. */
+  message?: string
+}
+/** Register a rule with a callback `format(text, context) => string`. */
+export function registerRule(name: string, format: (text: string, context: string) => string, options?: RuleOptions): void
+/** Unregister a rule by name, returns `true` if the rule was registered. */
+export function unregisterRule(name: string): boolean
 export function run(args: Array<string>): Promise<void>
 export class Ignorer {
   constructor(workDir: string)
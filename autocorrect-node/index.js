@@ -252,11 +252,13 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { Ignorer, format, formatFor, lintFor, loadConfig, run } = nativeBinding
+const { Ignorer, format, formatFor, lintFor, loadConfig, registerRule, unregisterRule, run } = nativeBinding
 
 module.exports.Ignorer = Ignorer
 module.exports.format = format
 module.exports.formatFor = formatFor
 module.exports.lintFor = lintFor
 module.exports.loadConfig = loadConfig
+module.exports.registerRule = registerRule
+module.exports.unregisterRule = unregisterRule
 module.exports.run = run
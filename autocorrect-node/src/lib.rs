@@ -3,6 +3,21 @@
 #[macro_use]
 extern crate napi_derive;
 
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    collections::HashMap,
+};
+
+use napi::{Env, JsFunction, Ref};
+
+thread_local! {
+    /// The JavaScript callbacks of the registered rules, they can only be called in the JavaScript thread.
+    static RULE_CALLBACKS: RefCell<HashMap<String, Ref<()>>> = RefCell::new(HashMap::new());
+    /// The env of the current call, for calling the rule callbacks.
+    static CURRENT_ENV: Cell<Option<napi::sys::napi_env>> = const { Cell::new(None) };
+}
+
 #[napi(object)]
 pub struct LineResult {
     pub l: u32,
@@ -10,6 +25,7 @@ pub struct LineResult {
     pub new: String,
     pub old: String,
     pub severity: u32,
+    pub message: String,
 }
 
 #[napi(object)]
@@ -39,20 +55,28 @@ impl Ignorer {
     }
 }
 
+/// Run with the env of the current call, so the rule callbacks can be called.
+fn with_env<T>(env: Env, f: impl FnOnce() -> T) -> T {
+    let last_env = CURRENT_ENV.replace(Some(env.raw()));
+    let out = f();
+    CURRENT_ENV.set(last_env);
+    out
+}
+
 #[napi]
-pub fn format(text: String) -> String {
-    autocorrect::format(&text)
+pub fn format(env: Env, text: String) -> String {
+    with_env(env, || autocorrect::format(&text))
 }
 
 #[napi]
-pub fn format_for(text: String, filepath: String) -> String {
-    let result = autocorrect::format_for(&text, &filepath);
+pub fn format_for(env: Env, text: String, filepath: String) -> String {
+    let result = with_env(env, || autocorrect::format_for(&text, &filepath));
     result.out
 }
 
 #[napi]
-pub fn lint_for(text: String, filepath: String) -> LintResult {
-    let result = autocorrect::lint_for(&text, &filepath);
+pub fn lint_for(env: Env, text: String, filepath: String) -> LintResult {
+    let result = with_env(env, || autocorrect::lint_for(&text, &filepath));
     LintResult {
         filepath,
         error: result.error,
@@ -65,16 +89,161 @@ pub fn lint_for(text: String, filepath: String) -> LintResult {
                 new: l.new.clone(),
                 old: l.old.clone(),
                 severity: l.severity as u32,
+                message: l.message.clone(),
             })
             .collect::<_>(),
     }
 }
 
+/// Lint a file content and output the report in the `format`, e.g.: `json`, `rdjson`, `checkstyle`, `junit`.
+#[napi]
+pub fn lint_report_for(
+    env: Env,
+    text: String,
+    filepath: String,
+    format: String,
+) -> napi::Result<String> {
+    let mut result = with_env(env, || autocorrect::lint_for(&text, &filepath));
+    result.filepath = filepath;
+    autocorrect::to_lint_results_report(vec![result], &format).map_err(napi::Error::from_reason)
+}
+
 #[napi]
 pub fn load_config(config_str: String) {
     autocorrect::config::load(&config_str).unwrap();
 }
 
+#[napi(object)]
+pub struct RuleOptions {
+    /// `after` (default) to format the whole line, or `part` to format each part of the line has CJK.
+    pub phase: Option<String>,
+    /// The severity when the rule is not in the `rules` config, 1: error (default), 2: warning.
+    pub severity: Option<u32>,
+    /// The message for the lint results.
+    pub message: Option<String>,
+}
+
+/// A rule with the JavaScript callback to format the text.
+struct NodeRule {
+    name: String,
+    phase: autocorrect::Phase,
+    severity: autocorrect::config::SeverityMode,
+    message: Option<String>,
+}
+
+impl NodeRule {
+    /// Call the JavaScript callback, it can only be called in `with_env`.
+    fn call(&self, input: &str, context: &autocorrect::Context) -> napi::Result<String> {
+        let Some(raw_env) = CURRENT_ENV.get() else {
+            return Err(napi::Error::from_reason(format!(
+                "The rule `{}` can only be called by the format or lint functions in the JavaScript thread.",
+                self.name
+            )));
+        };
+        // SAFETY: `CURRENT_ENV` is thread local, it is only set by `with_env` to the env of the current call
+        // from JavaScript, and restored before the call returns, so the env is valid in this thread now.
+        let env = unsafe { Env::from_raw(raw_env) };
+
+        RULE_CALLBACKS.with_borrow(|callbacks| {
+            let callback = callbacks.get(&self.name).ok_or_else(|| {
+                napi::Error::from_reason(format!("The rule `{}` is not registered.", self.name))
+            })?;
+            let callback: JsFunction = env.get_reference_value(callback)?;
+            let args = [
+                env.create_string(input)?,
+                env.create_string(context.name())?,
+            ];
+            callback
+                .call(None, &args)?
+                .coerce_to_string()?
+                .into_utf8()?
+                .into_owned()
+        })
+    }
+}
+
+impl autocorrect::Rule for NodeRule {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn phase(&self) -> autocorrect::Phase {
+        self.phase
+    }
+
+    fn default_severity(&self) -> autocorrect::config::SeverityMode {
+        self.severity
+    }
+
+    fn format<'a>(&self, input: &'a str, context: &autocorrect::Context) -> Cow<'a, str> {
+        match self.call(input, context) {
+            Ok(out) if out != input => Cow::Owned(out),
+            Ok(_) => Cow::Borrowed(input),
+            Err(err) => {
+                // The text is kept, the rule can't return the error
+                eprintln!("AutoCorrect: {}", err);
+                Cow::Borrowed(input)
+            }
+        }
+    }
+
+    fn messages(&self, _input: &str, _context: &autocorrect::Context) -> Vec<String> {
+        self.message.iter().cloned().collect()
+    }
+}
+
+/// Register a rule with a callback `format(text, context) => string`.
+#[napi(
+    ts_args_type = "name: string, format: (text: string, context: string) => string, options?: RuleOptions"
+)]
+pub fn register_rule(
+    env: Env,
+    name: String,
+    format: JsFunction,
+    options: Option<RuleOptions>,
+) -> napi::Result<()> {
+    let options = options.unwrap_or(RuleOptions {
+        phase: None,
+        severity: None,
+        message: None,
+    });
+    let phase = options
+        .phase
+        .as_deref()
+        .unwrap_or("after")
+        .parse()
+        .map_err(|err: String| napi::Error::from_reason(err))?;
+    let severity = match options.severity {
+        Some(2) => autocorrect::config::SeverityMode::Warning,
+        Some(0) => autocorrect::config::SeverityMode::Off,
+        _ => autocorrect::config::SeverityMode::Error,
+    };
+
+    let callback = env.create_reference(format)?;
+    if let Some(mut last) = RULE_CALLBACKS.with_borrow_mut(|c| c.insert(name.clone(), callback)) {
+        last.unref(env)?;
+    }
+
+    autocorrect::Engine::register_rule(NodeRule {
+        name,
+        phase,
+        severity,
+        message: options.message,
+    });
+
+    Ok(())
+}
+
+/// Unregister a rule by name, returns `true` if the rule was registered.
+#[napi]
+pub fn unregister_rule(env: Env, name: String) -> napi::Result<bool> {
+    if let Some(mut callback) = RULE_CALLBACKS.with_borrow_mut(|c| c.remove(&name)) {
+        callback.unref(env)?;
+    }
+
+    Ok(autocorrect::Engine::unregister_rule(&name))
+}
+
 #[napi]
 pub async fn run(args: Vec<String>) {
     // skip 2 args:
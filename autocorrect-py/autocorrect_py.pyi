@@ -1,4 +1,4 @@
-from typing import List
+from typing import Callable, List, Optional
 
 
 class Severity:
@@ -13,6 +13,7 @@ class LineResult:
   new: str
   old: str
   severity: Severity
+  message: str
 
 
 class LintResult:
@@ -31,3 +32,22 @@ def format_for(raw: str, filename_or_ext: str) -> str: ...
 
 # Lint a file content with filetype.
 def lint_for(raw: str, filename_or_ext: str) -> LintResult: ...
+
+# Lint a file content and output the report in the `format`, e.g.: "json", "rdjson", "checkstyle", "junit".
+def lint_report_for(raw: str, filename_or_ext: str, format: str) -> str: ...
+
+# Register a rule with a callback `format(text, context) -> str`.
+#
+# The `context` is the name of the text context, e.g.: "prose", "comment", "string-literal".
+# The `phase` is "after" (default) to format the whole line, or "part" to format each part of the line has CJK.
+# The `severity` is used when the rule is not in the `rules` config, the `message` is for the lint results.
+def register_rule(
+    name: str,
+    format: Callable[[str, str], str],
+    phase: Optional[str] = None,
+    severity: Optional[Severity] = None,
+    message: Optional[str] = None,
+) -> None: ...
+
+# Unregister a rule by name, returns `True` if the rule was registered.
+def unregister_rule(name: str) -> bool: ...
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::path::PathBuf;
 
 use pyo3::exceptions::PyValueError;
@@ -25,6 +26,8 @@ struct LineResult {
     pub old: String,
     #[pyo3(get)]
     pub severity: Severity,
+    #[pyo3(get)]
+    pub message: String,
 }
 
 #[pymethods]
@@ -120,6 +123,7 @@ fn lint_for(raw: &str, filename_or_ext: &str) -> PyResult<LintResult> {
                     2 => Severity::Warning,
                     _ => unreachable!(),
                 },
+                message: l.message,
             })
             .collect();
         let lint_result = LintResult {
@@ -132,11 +136,102 @@ fn lint_for(raw: &str, filename_or_ext: &str) -> PyResult<LintResult> {
     }
 }
 
+/// Lint a file content and output the report in the `format`, e.g.: `json`, `rdjson`, `checkstyle`, `junit`.
+#[pyfunction]
+fn lint_report_for(raw: &str, filename_or_ext: &str, format: &str) -> PyResult<String> {
+    let mut result = autocorrect::lint_for(raw, filename_or_ext);
+    result.filepath = filename_or_ext.to_owned();
+    autocorrect::to_lint_results_report(vec![result], format).map_err(PyValueError::new_err)
+}
+
 #[pyfunction]
 fn load_config(config_str: &str) {
     autocorrect::config::load(config_str).unwrap();
 }
 
+/// A rule with the Python callback to format the text.
+struct PyRule {
+    name: String,
+    phase: autocorrect::Phase,
+    severity: autocorrect::config::SeverityMode,
+    message: Option<String>,
+    format: PyObject,
+}
+
+impl autocorrect::Rule for PyRule {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn phase(&self) -> autocorrect::Phase {
+        self.phase
+    }
+
+    fn default_severity(&self) -> autocorrect::config::SeverityMode {
+        self.severity
+    }
+
+    fn format<'a>(&self, input: &'a str, context: &autocorrect::Context) -> Cow<'a, str> {
+        Python::with_gil(|py| {
+            let out = self
+                .format
+                .call1(py, (input, context.name()))
+                .and_then(|out| out.extract::<String>(py));
+
+            match out {
+                Ok(out) if out != input => Cow::Owned(out),
+                Ok(_) => Cow::Borrowed(input),
+                Err(err) => {
+                    err.print(py);
+                    Cow::Borrowed(input)
+                }
+            }
+        })
+    }
+
+    fn messages(&self, _input: &str, _context: &autocorrect::Context) -> Vec<String> {
+        self.message.iter().cloned().collect()
+    }
+}
+
+/// Register a rule with a callback `format(text, context) -> str`.
+///
+/// The `phase` is `"after"` (default) or `"part"`, the `severity` is used when the rule is not in the `rules` config.
+#[pyfunction]
+fn register_rule(
+    name: String,
+    format: PyObject,
+    phase: Option<&str>,
+    severity: Option<Severity>,
+    message: Option<String>,
+) -> PyResult<()> {
+    let phase = phase
+        .unwrap_or("after")
+        .parse()
+        .map_err(PyValueError::new_err)?;
+    let severity = match severity.unwrap_or(Severity::Error) {
+        Severity::Pass => autocorrect::config::SeverityMode::Off,
+        Severity::Error => autocorrect::config::SeverityMode::Error,
+        Severity::Warning => autocorrect::config::SeverityMode::Warning,
+    };
+
+    autocorrect::Engine::register_rule(PyRule {
+        name,
+        phase,
+        severity,
+        message,
+        format,
+    });
+
+    Ok(())
+}
+
+/// Unregister a rule by name, returns `True` if the rule was registered.
+#[pyfunction]
+fn unregister_rule(name: &str) -> bool {
+    autocorrect::Engine::unregister_rule(name)
+}
+
 /// Automatically add whitespace between CJK (Chinese, Japanese, Korean)
 /// and half-width characters (alphabetical letters, numerical digits and symbols).
 #[pymodule]
@@ -149,7 +244,10 @@ fn autocorrect_py(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(format, m)?)?;
     m.add_function(wrap_pyfunction!(format_for, m)?)?;
     m.add_function(wrap_pyfunction!(lint_for, m)?)?;
+    m.add_function(wrap_pyfunction!(lint_report_for, m)?)?;
     m.add_function(wrap_pyfunction!(load_config, m)?)?;
+    m.add_function(wrap_pyfunction!(register_rule, m)?)?;
+    m.add_function(wrap_pyfunction!(unregister_rule, m)?)?;
 
     Ok(())
 }
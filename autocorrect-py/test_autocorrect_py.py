@@ -47,3 +47,20 @@ def test_ignorer():
     assert ignorer.is_ignored("target/foo/bar") == True
     assert ignorer.is_ignored("Cagro.toml") == False
     assert ignorer.is_ignored("autocorrect-rb.gemspec") == False
+
+
+def test_register_rule():
+    autocorrect.register_rule(
+        "no-ellipsis",
+        lambda text, context: text.replace("...", "……"),
+        severity=autocorrect.Severity.Warning,
+        message="Use `……` for ellipsis",
+    )
+
+    output = autocorrect.lint_for("你好...", "text")
+    assert len(output.lines) == 1
+    assert output.lines[0].new == "你好……"
+    assert output.lines[0].message == "Use `……` for ellipsis"
+
+    assert autocorrect.unregister_rule("no-ellipsis")
+    assert not autocorrect.lint_for("你好...", "text").lines
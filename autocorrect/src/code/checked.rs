@@ -0,0 +1,196 @@
+// autocorrect: false
+use super::format_for;
+use crate::result::{FormatResult, LineResult, LintResult, Severity};
+
+/// The result of `format_for_checked`, the output is idempotent if the second pass changes nothing.
+pub struct CheckedResult {
+    /// The result of the first pass.
+    pub result: FormatResult,
+    /// The output of the second pass, `None` if it is the same as the first pass.
+    pub second: Option<String>,
+    /// The output of the last pass, it is the fixed point if `converged`.
+    pub out: String,
+    /// The number of the format passes, include the first pass.
+    pub passes: usize,
+    /// The output stopped changing within the max iterations.
+    pub converged: bool,
+    /// The parse error of the later passes, the output of the previous pass can't be parsed again.
+    pub error: String,
+}
+
+impl CheckedResult {
+    /// The second pass changes nothing, and the output can be parsed again.
+    pub fn is_idempotent(&self) -> bool {
+        self.second.is_none() && self.error.is_empty()
+    }
+
+    /// The lines changed by the second pass, as a `LintResult` for output like the lint.
+    pub fn to_lint_result(&self, filepath: &str) -> LintResult {
+        let mut result = LintResult::new(&self.result.out);
+        result.filepath = String::from(filepath);
+        result.error = self.error.clone();
+
+        let Some(second) = &self.second else {
+            return result;
+        };
+
+        let message = "Not idempotent, the line is changed again by the second format.";
+        let diffs = diff::lines(&self.result.out, second);
+        // The line number in the output of the first pass
+        let mut line = 0;
+        let mut i = 0;
+        while i < diffs.len() {
+            let (old, new) = match (&diffs[i], diffs.get(i + 1)) {
+                (diff::Result::Both(..), _) => {
+                    line += 1;
+                    i += 1;
+                    continue;
+                }
+                (diff::Result::Left(old), Some(diff::Result::Right(new))) => {
+                    line += 1;
+                    i += 2;
+                    (*old, *new)
+                }
+                (diff::Result::Left(old), _) => {
+                    line += 1;
+                    i += 1;
+                    (*old, "")
+                }
+                // A new line is inserted after the line
+                (diff::Result::Right(new), _) => {
+                    i += 1;
+                    ("", *new)
+                }
+            };
+
+            result.lines.push(LineResult {
+                line: line.max(1),
+                col: first_diff_col(old, new),
+                old: old.to_owned(),
+                new: new.to_owned(),
+                severity: Severity::Error,
+                message: message.to_owned(),
+                ..Default::default()
+            });
+        }
+
+        result
+    }
+}
+
+/// The 1-based column of the first different char.
+fn first_diff_col(old: &str, new: &str) -> usize {
+    old.chars()
+        .zip(new.chars())
+        .take_while(|(a, b)| a == b)
+        .count()
+        + 1
+}
+
+/// Format a file content with filetype, and format the output again to verify it is idempotent.
+///
+/// The output is formatted until it stops changing, at most `max_iterations` passes (at least 2).
+///
+/// ```
+/// let result = autocorrect::format_for_checked("Hello世界.", "text", 2);
+/// assert!(result.is_idempotent());
+/// assert_eq!("Hello 世界。", result.out);
+/// ```
+pub fn format_for_checked(
+    raw: &str,
+    filename_or_ext: &str,
+    max_iterations: usize,
+) -> CheckedResult {
+    let result = format_for(raw, filename_or_ext);
+    let mut checked = CheckedResult {
+        out: result.out.clone(),
+        second: None,
+        passes: 1,
+        converged: true,
+        error: String::new(),
+        result,
+    };
+
+    if checked.result.has_error() {
+        return checked;
+    }
+
+    checked.converged = false;
+    while checked.passes < max_iterations.max(2) {
+        let next = format_for(&checked.out, filename_or_ext);
+        checked.passes += 1;
+
+        if next.has_error() {
+            checked.error = next.error;
+            break;
+        }
+
+        if next.out == checked.out {
+            checked.converged = true;
+            break;
+        }
+
+        if checked.passes == 2 {
+            checked.second = Some(next.out.clone());
+        }
+        checked.out = next.out;
+    }
+
+    checked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_for_checked() {
+        let result = format_for_checked("Hello世界.", "text", 2);
+        assert!(result.is_idempotent());
+        assert!(result.converged);
+        assert_eq!(2, result.passes);
+        assert!(result.to_lint_result("foo.txt").lines.is_empty());
+
+        // Found by the property tests, it was changed again by `fullwidth` in the second pass
+        let result = format_for_checked("中文!한국어", "text", 5);
+        assert!(result.is_idempotent());
+        assert!(result.converged);
+        assert_eq!(2, result.passes);
+        assert_eq!("中文！한국어", result.out);
+    }
+
+    #[test]
+    fn test_to_lint_result_with_lines_changed() {
+        let result = CheckedResult {
+            result: FormatResult {
+                out: "a\nb\nc".to_owned(),
+                ..FormatResult::new("")
+            },
+            second: Some("a\nB\nc\nd".to_owned()),
+            out: String::new(),
+            passes: 2,
+            converged: false,
+            error: String::new(),
+        };
+
+        let lint_result = result.to_lint_result("foo.txt");
+        assert_eq!("foo.txt", lint_result.filepath);
+        assert!(lint_result
+            .lines
+            .iter()
+            .all(|l| l.severity == Severity::Error));
+
+        let lines = lint_result
+            .lines
+            .into_iter()
+            .map(|l| (l.line, l.col, l.old, l.new))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (2, 1, "b".to_owned(), "B".to_owned()),
+                (3, 1, "".to_owned(), "d".to_owned())
+            ],
+            lines
+        );
+    }
+}
@@ -2,11 +2,12 @@
 use super::*;
 use crate::config::toggle;
 pub use crate::result::*;
-use crate::rule::CJK_RE;
+use crate::rule::{Context, CJK_RE};
 use crate::Config;
-use pest::error::Error;
+use pest::error::{Error, ErrorVariant, LineColLocation};
 use pest::iterators::{Pair, Pairs};
 use pest::RuleType;
+use std::borrow::Cow;
 use std::result::Result;
 
 trait RuleTypeToString {
@@ -27,19 +28,197 @@ pub fn format_pairs<R: RuleType, O: Results>(out: O, pairs: Result<Pairs<R>, Err
 
     match pairs {
         Ok(pairs) => {
+            toggle_lines(&mut out, pairs.clone());
+
+            let mut prev_rule = None;
             for pair in pairs {
-                format_pair(&mut out, pair);
+                let rule_name = pair.as_rule().to_string();
+                format_pair(&mut out, pair, sibling_context(prev_rule.as_deref(), None));
+                prev_rule = Some(rule_name);
             }
+
+            report_directives(&mut out);
         }
-        Err(_err) => {
-            out.error(&_err.to_string());
+        Err(err) => {
+            out.parse_error(to_parse_error(&err));
         }
     }
 
     out
 }
 
-fn format_pair<R: RuleType, O: Results>(results: &mut O, pair: Pair<R>) {
+/// Convert the pest error into the structured parse error.
+pub(crate) fn to_parse_error<R: RuleType>(err: &Error<R>) -> ParseError {
+    let (line, col) = match err.line_col {
+        LineColLocation::Pos(pos) => pos,
+        LineColLocation::Span(start, _) => start,
+    };
+
+    let expected = match &err.variant {
+        ErrorVariant::ParsingError { positives, .. } => {
+            positives.iter().map(|rule| rule.to_string()).collect()
+        }
+        ErrorVariant::CustomError { .. } => vec![],
+    };
+
+    ParseError {
+        line,
+        col,
+        grammar: grammar_name::<R>().to_owned(),
+        expected,
+        message: err.variant.message().to_string(),
+    }
+}
+
+/// The name of the grammar, the type name of the Rule is like `autocorrect::code::markdown::Rule`
+fn grammar_name<R: RuleType>() -> &'static str {
+    std::any::type_name::<R>()
+        .rsplit("::")
+        .nth(1)
+        .unwrap_or_default()
+}
+
+/// Keep the placeholders for the i18n files, e.g.: `%s`, `{name}`.
+fn keep_placeholders<R: RuleType>() -> bool {
+    matches!(grammar_name::<R>(), "yaml" | "json" | "strings" | "gettext")
+}
+
+/// Collect the line toggles in the comments before format, because `autocorrect-disable-line` is after the text of the line.
+fn toggle_lines<R: RuleType, O: Results>(results: &mut O, pairs: Pairs<R>) {
+    for pair in pairs.flatten() {
+        let rule_name = pair.as_rule().to_string();
+        if rule_name != "comment" && rule_name != "COMMENT" {
+            continue;
+        }
+
+        if let Some((scope, line_toggle)) = toggle::parse_line(pair.as_str()) {
+            let (line, _) = pair.line_col();
+            let line = match scope {
+                toggle::LineScope::Line => line,
+                // The line after the end of the comment, the comment may be multiple lines.
+                toggle::LineScope::NextLine => {
+                    line + pair.as_str().trim_end().matches('\n').count() + 1
+                }
+            };
+            if let Some(directives) = results.directives_mut() {
+                directives.push(toggle::Directive::new(
+                    toggle::DirectiveKind::Line(line),
+                    line_toggle.clone(),
+                    pair.as_str(),
+                    pair.line_col(),
+                ));
+            }
+            results.toggle_line(line, &line_toggle);
+        }
+    }
+}
+
+/// Record the region directive in the comment, for the `reportUnusedDisableDirectives` config.
+fn record_directive<O: Results>(
+    results: &mut O,
+    part: &str,
+    line_col: (usize, usize),
+    new_toggle: &toggle::Toggle,
+) {
+    let Some(directives) = results.directives_mut() else {
+        return;
+    };
+
+    let kind = match new_toggle {
+        toggle::Toggle::Disable(_) if toggle::is_file_disable(part) => toggle::DirectiveKind::File,
+        toggle::Toggle::Disable(_) => toggle::DirectiveKind::Region,
+        toggle::Toggle::Enable(_) => {
+            // Close the disabled regions of the enabled rules
+            directives
+                .iter_mut()
+                .filter(|d| d.is_closed_by(new_toggle))
+                .for_each(|d| d.closed = true);
+            toggle::DirectiveKind::Enable
+        }
+        toggle::Toggle::None => return,
+    };
+
+    directives.push(toggle::Directive::new(
+        kind,
+        new_toggle.clone(),
+        part,
+        line_col,
+    ));
+}
+
+/// Mark the directives applied to the line as used, if the line has lint results without the directives.
+fn mark_suppressed<O: Results>(results: &mut O, line_str: &str, line: usize, context: Context) {
+    let Some(directives) = results.directives_mut() else {
+        return;
+    };
+    if !directives.iter().any(|d| d.is_applied(line)) {
+        return;
+    }
+
+    let result = crate::rule::format_or_lint_with_context(
+        line_str,
+        true,
+        &Default::default(),
+        line_context(line_str, context),
+    );
+    for directive in directives.iter_mut().filter(|d| d.is_applied(line)) {
+        if directive.is_suppressed(&result.rules) {
+            directive.used = true;
+        }
+    }
+}
+
+/// Report the unused directives, the unknown rules in the directives, and the regions not enabled again.
+fn report_directives<O: Results>(results: &mut O) {
+    let Some(directives) = results.directives_mut() else {
+        return;
+    };
+    let directives = std::mem::take(directives);
+    if directives.is_empty() {
+        return;
+    }
+
+    let rule_names = crate::rule::rules()
+        .into_iter()
+        .map(|r| r.name)
+        .collect::<Vec<_>>();
+
+    for directive in directives {
+        let mut messages = vec![];
+        for name in directive.rule_names() {
+            if !rule_names.iter().any(|r| r == name) {
+                messages.push(format!("Unknown rule `{name}` in the directive."));
+            }
+        }
+
+        if directive.kind != toggle::DirectiveKind::Enable && !directive.used {
+            messages.push("Unused directive, no problems were suppressed.".to_string());
+        }
+
+        if directive.kind == toggle::DirectiveKind::Region && !directive.closed {
+            messages.push(
+                "The disabled region is still open at the end of file, use `autocorrect-enable` to close it.".to_string(),
+            );
+        }
+
+        if messages.is_empty() {
+            continue;
+        }
+
+        results.push(LineResult {
+            line: directive.line,
+            col: directive.col,
+            old: directive.text.clone(),
+            new: directive.text,
+            severity: Severity::Warning,
+            message: messages.join("\n"),
+            ..Default::default()
+        });
+    }
+}
+
+/// The `context` is from the parent pairs, `None` means the pair is not in any markup block, e.g.: code.
+fn format_pair<R: RuleType, O: Results>(results: &mut O, pair: Pair<R>, context: Option<Context>) {
     let rule = pair.as_rule();
     let rule_name = rule.to_string();
     let rule_name = rule_name.as_str();
@@ -48,7 +227,7 @@ fn format_pair<R: RuleType, O: Results>(results: &mut O, pair: Pair<R>) {
     match rule_name {
         "string" | "link_string" | "mark_string" | "text" | "inner_text" | "comment"
         | "COMMENT" => {
-            format_or_lint(results, rule_name, pair);
+            format_or_lint(results, rule_name, pair, context);
         }
         "inline_style" | "inline_javascript" | "codeblock" => {
             format_or_lint_for_inline_scripts(results, pair, rule_name);
@@ -56,7 +235,8 @@ fn format_pair<R: RuleType, O: Results>(results: &mut O, pair: Pair<R>) {
         _ => {
             let mut has_child = false;
             let pair_str = pair.as_str();
-            let sub_pairs = pair.into_inner();
+            let context = block_context(rule_name, &pair, context);
+            let mut sub_pairs = pair.into_inner().peekable();
 
             // Special hotfix for Markdown block / paragraph / blockquote
             // If they has CJK chars, disable `halfwidth-punctuation` rule temporary.
@@ -66,9 +246,39 @@ fn format_pair<R: RuleType, O: Results>(results: &mut O, pair: Pair<R>) {
                 results.toggle_merge_for_codeblock();
             }
 
-            for child in sub_pairs {
-                format_pair(results, child);
+            let mut prev_rule = None;
+            while let Some(child) = sub_pairs.next() {
                 has_child = true;
+                let child_rule_name = child.as_rule().to_string();
+
+                // Heading marker without space, e.g.: `##标题`, format them together for `heading-space` rule.
+                if context == Some(Context::Heading)
+                    && is_heading_marker(&child)
+                    && let Some(next) = sub_pairs.next_if(|next| {
+                        next.as_rule().to_string() == "string"
+                            && next.as_span().start() == child.as_span().end()
+                    })
+                {
+                    let input = child.get_input();
+                    let part = &input[child.as_span().start()..next.as_span().end()];
+                    let context = heading_context(input, next.as_span().end());
+                    format_or_lint_part(
+                        results,
+                        "string",
+                        part,
+                        child.line_col(),
+                        context,
+                        keep_placeholders::<R>(),
+                    );
+                    continue;
+                }
+
+                format_pair(
+                    results,
+                    child,
+                    sibling_context(prev_rule.as_deref(), context),
+                );
+                prev_rule = Some(child_rule_name);
             }
 
             // Restore toggle if last_toggle is some
@@ -83,36 +293,168 @@ fn format_pair<R: RuleType, O: Results>(results: &mut O, pair: Pair<R>) {
     };
 }
 
+/// The context of the block, for the text in it.
+fn block_context<R: RuleType>(
+    rule_name: &str,
+    pair: &Pair<R>,
+    context: Option<Context>,
+) -> Option<Context> {
+    if is_heading(rule_name, pair) {
+        return Some(Context::Heading);
+    }
+
+    match rule_name {
+        // Markdown front matter, e.g.: `title: Hello`
+        "meta_pair" => Some(Context::FrontMatterValue),
+        // The markup blocks, e.g.: paragraph in Markdown, AsciiDoc or LaTeX
+        "block" | "paragraph" | "para" | "html" => context.or(Some(Context::Prose)),
+        _ => context,
+    }
+}
+
+/// The text after the table cell separator `|` is in a table cell, e.g.: `| Cell |`.
+fn sibling_context(prev_rule: Option<&str>, context: Option<Context>) -> Option<Context> {
+    match prev_rule {
+        Some("td_tag") => Some(Context::TableCell),
+        _ => context,
+    }
+}
+
+/// The table row in Markdown is parsed as a paragraph, e.g.: `| Cell | Cell |`.
+fn line_context(line: &str, context: Context) -> Context {
+    if context == Context::Prose && line.trim_start().starts_with('|') {
+        return Context::TableCell;
+    }
+
+    context
+}
+
+/// The context of the text, the text not in any markup block is a string literal in code.
+fn text_context(rule_name: &str, context: Option<Context>) -> Context {
+    match rule_name {
+        "comment" | "COMMENT" => Context::Comment,
+        "link_string" => Context::LinkText,
+        "text" | "mark_string" => context.unwrap_or(Context::Prose),
+        _ => context.unwrap_or(Context::StringLiteral),
+    }
+}
+
+/// Is the pair a heading, e.g.: `# Heading` in Markdown, `= Heading` in AsciiDoc, `\section{Heading}` in LaTeX.
+fn is_heading<R: RuleType>(rule_name: &str, pair: &Pair<R>) -> bool {
+    match rule_name {
+        // Markdown, the `block_item` also includes the blockquote `>`.
+        "block_item" => pair.as_str().starts_with('#'),
+        // AsciiDoc
+        "heading" => true,
+        // LaTeX
+        "function" => {
+            let name = pair
+                .as_str()
+                .split(['{', '[', ' '])
+                .next()
+                .unwrap_or_default();
+            matches!(
+                name,
+                "\\part"
+                    | "\\chapter"
+                    | "\\section"
+                    | "\\subsection"
+                    | "\\subsubsection"
+                    | "\\title"
+            )
+        }
+        _ => false,
+    }
+}
+
+fn is_heading_marker<R: RuleType>(pair: &Pair<R>) -> bool {
+    let rule_name = pair.as_rule().to_string();
+    matches!(rule_name.as_str(), "block_prefix" | "heading_tag") && !pair.as_str().ends_with(' ')
+}
+
+/// Only the text at the end of a heading is in the `Heading` context, so the heading rules only check the end once.
+fn heading_context(input: &str, end: usize) -> Context {
+    let rest = &input[end..];
+    if rest.is_empty() || rest.starts_with(['\n', '\r']) {
+        Context::Heading
+    } else {
+        Context::Prose
+    }
+}
+
 /// Format or Lint a matched item
-pub fn format_or_lint<R: RuleType, O: Results>(results: &mut O, rule_name: &str, pair: Pair<R>) {
-    let part = pair.as_str();
-    let (line, col) = pair.line_col();
+pub fn format_or_lint<R: RuleType, O: Results>(
+    results: &mut O,
+    rule_name: &str,
+    pair: Pair<R>,
+    context: Option<Context>,
+) {
+    let context = match text_context(rule_name, context) {
+        Context::Heading => heading_context(pair.get_input(), pair.as_span().end()),
+        context => context,
+    };
+
+    format_or_lint_part(
+        results,
+        rule_name,
+        pair.as_str(),
+        pair.line_col(),
+        context,
+        keep_placeholders::<R>(),
+    );
+}
+
+fn format_or_lint_part<O: Results>(
+    results: &mut O,
+    rule_name: &str,
+    part: &str,
+    (line, col): (usize, usize),
+    context: Context,
+    placeholders: bool,
+) {
+    let format_or_lint_with_context = if placeholders {
+        crate::rule::format_or_lint_with_placeholders
+    } else {
+        crate::rule::format_or_lint_with_context
+    };
 
     // Check AutoCorrect enable/disable toggle marker
     // If disable results.is_enabled() will be false
     if rule_name == "comment" || rule_name == "COMMENT" {
-        results.toggle(&toggle::parse(part));
+        let new_toggle = toggle::parse(part);
+        record_directive(results, part, (line, col), &new_toggle);
+        results.toggle(&new_toggle);
     }
 
-    let disabled_rules = results.get_toggle().disable_rules();
     if results.is_lint() {
-        // Skip lint if AutoCorrect disabled
-        if !results.is_enabled() {
+        // Skip lint if AutoCorrect disabled, unless to check the disable directives are used
+        if !results.is_enabled() && results.directives_mut().is_none() {
             return;
         }
 
-        let lines = part.split('\n');
-
         // sub line in a part
-        let mut sub_line = 0;
-        for line_str in lines {
+        for (sub_line, line_str) in part.split('\n').enumerate() {
+            let current_line = line + sub_line;
+
+            // Skip the line disabled by `autocorrect-disable-next-line` or `autocorrect-disable-line`
+            let toggle = results.get_line_toggle(current_line);
+            if toggle != toggle::Toggle::default() {
+                mark_suppressed(results, line_str, current_line, context);
+            }
+            if !toggle.match_rule("").unwrap_or(true) {
+                continue;
+            }
+
             // format trimmed string
-            let line_result =
-                crate::rule::format_or_lint_with_disable_rules(line_str, true, &disabled_rules);
+            let line_result = format_or_lint_with_context(
+                line_str,
+                true,
+                &toggle.disable_rules(),
+                line_context(line_str, context),
+            );
 
             // skip, when no difference
             if line_result.severity.is_pass() {
-                sub_line += 1;
                 continue;
             }
 
@@ -124,7 +466,6 @@ pub fn format_or_lint<R: RuleType, O: Results>(results: &mut O, rule_name: &str,
             trimmed = trimmed.trim_end();
             // println!("{}||{},{}", line_result.out, trimmed, new_line.eq(trimmed));
 
-            let current_line = line + sub_line;
             let current_col = if sub_line > 0 {
                 // col will equal number of removed leading whitespace
                 leading_spaces + 1
@@ -139,9 +480,10 @@ pub fn format_or_lint<R: RuleType, O: Results>(results: &mut O, rule_name: &str,
                 old: String::from(trimmed),
                 new: line_result.out.trim().to_string(),
                 severity: line_result.severity,
+                message: line_result.messages.join("\n"),
+                rules: line_result.rules.clone(),
+                node_line: line,
             });
-
-            sub_line += 1;
         }
     } else {
         let mut new_part = String::from(part);
@@ -151,9 +493,21 @@ pub fn format_or_lint<R: RuleType, O: Results>(results: &mut O, rule_name: &str,
             let lines = part.split('\n');
 
             new_part = lines
-                .into_iter()
-                .map(|l| {
-                    crate::rule::format_or_lint_with_disable_rules(l, false, &disabled_rules).out
+                .enumerate()
+                .map(|(sub_line, l)| {
+                    // Keep the line disabled by the line toggles
+                    let toggle = results.get_line_toggle(line + sub_line);
+                    if !toggle.match_rule("").unwrap_or(true) {
+                        return Cow::Borrowed(l);
+                    }
+
+                    format_or_lint_with_context(
+                        l,
+                        false,
+                        &toggle.disable_rules(),
+                        line_context(l, context),
+                    )
+                    .out
                 })
                 .collect::<Vec<_>>()
                 .join("\n");
@@ -165,6 +519,7 @@ pub fn format_or_lint<R: RuleType, O: Results>(results: &mut O, rule_name: &str,
             old: String::from(part),
             new: new_part,
             severity: Severity::Pass,
+            ..Default::default()
         });
     }
 }
@@ -204,13 +559,18 @@ fn format_or_lint_for_inline_scripts<R: RuleType, O: Results>(
         };
 
         if let Some(result) = sub_result {
-            if result.has_error() {
+            if let Some(mut parse_error) = result.parse_error {
+                parse_error.line += base_line - 1;
+                results.parse_error(parse_error);
+            } else if result.has_error() {
                 results.error(&result.error);
             }
 
             for mut line in result.lines {
                 // Inline script's lines need add base_line - 1 offset.
                 line.line += base_line - 1;
+                // The whole inline script is the node in the parent file.
+                line.node_line = base_line;
                 results.push(line);
             }
         }
@@ -250,6 +610,7 @@ fn format_or_lint_for_inline_scripts<R: RuleType, O: Results>(
             old: String::from(part),
             new: new_part,
             severity: Severity::Pass,
+            ..Default::default()
         });
     }
 }
@@ -300,6 +661,77 @@ mod tests {
     use indoc::indoc;
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn test_parse_error() {
+        use super::super::json::Rule;
+
+        let raw = "{\n  \"a\": }";
+        let err = Error::new_from_pos(
+            ErrorVariant::ParsingError {
+                positives: vec![Rule::string, Rule::object],
+                negatives: vec![],
+            },
+            pest::Position::new(raw, 9).unwrap(),
+        );
+
+        let result = format_pairs::<Rule, _>(LintResult::new(raw), Err(err.clone()));
+        let expected = ParseError {
+            line: 2,
+            col: 8,
+            grammar: "json".to_owned(),
+            expected: vec!["string".to_owned(), "object".to_owned()],
+            message: "expected string or object".to_owned(),
+        };
+        assert_eq!(Some(expected), result.parse_error);
+        assert_eq!("json parse error: expected string or object", result.error);
+        assert_eq!((2, 8), result.error_position());
+
+        let result = format_pairs::<Rule, _>(FormatResult::new(raw), Err(err));
+        assert_eq!("json parse error: expected string or object", result.error);
+        assert_eq!(raw, result.out);
+    }
+
+    #[test]
+    fn test_heading_context() {
+        let input = "# 标题：[链接](#link)\n## 标题。\n";
+        assert_eq!(Context::Prose, heading_context(input, 11));
+        assert_eq!(Context::Heading, heading_context(input, 26));
+        assert_eq!(Context::Heading, heading_context(input, input.len()));
+        assert_eq!(Context::Heading, heading_context("## 标题。\r\n", 12));
+    }
+
+    #[test]
+    fn test_text_context() {
+        assert_eq!(Context::Comment, text_context("COMMENT", None));
+        assert_eq!(
+            Context::Comment,
+            text_context("comment", Some(Context::Prose))
+        );
+        assert_eq!(
+            Context::LinkText,
+            text_context("link_string", Some(Context::Heading))
+        );
+        assert_eq!(Context::StringLiteral, text_context("string", None));
+        assert_eq!(Context::Prose, text_context("string", Some(Context::Prose)));
+        assert_eq!(Context::Prose, text_context("text", None));
+        assert_eq!(
+            Context::TableCell,
+            text_context(
+                "string",
+                sibling_context(Some("td_tag"), Some(Context::Prose))
+            )
+        );
+        assert_eq!(
+            Context::TableCell,
+            line_context("| 单元格 | 单元格 |", Context::Prose)
+        );
+        assert_eq!(Context::Comment, line_context("| 注释", Context::Comment));
+        assert_eq!(
+            Context::FrontMatterValue,
+            text_context("string", Some(Context::FrontMatterValue))
+        );
+    }
+
     #[test]
     fn test_format_for() {
         let mut raw = "// Hello你好";
@@ -446,4 +878,51 @@ mod tests {
         assert_eq!(result.lines[1].new, "// hello世界。");
         assert_eq!(result.lines[2].new, "// hello 世界.");
     }
+
+    #[test]
+    fn test_disable_line() {
+        let raw = indoc! {r#"
+        // autocorrect-disable-next-line
+        const a = "hello世界.";
+        const b = "hello世界."; // autocorrect-disable-line space-word
+        /*
+         * autocorrect-disable-next-line fullwidth
+         */
+        const c = "hello世界.";
+        const d = "hello世界.";
+        "#};
+
+        let expected = indoc! {r#"
+        // autocorrect-disable-next-line
+        const a = "hello世界.";
+        const b = "hello世界。"; // autocorrect-disable-line space-word
+        /*
+         * autocorrect-disable-next-line fullwidth
+         */
+        const c = "hello 世界.";
+        const d = "hello 世界。";
+        "#};
+
+        assert_eq!(expected, format_for(raw, "js").out);
+        let result = lint_for(raw, "js");
+        assert_eq!(
+            vec![3, 7, 8],
+            result.lines.iter().map(|l| l.line).collect::<Vec<_>>()
+        );
+
+        let raw = indoc! {r#"
+        <!-- autocorrect-disable-next-line -->
+        hello世界
+
+        hello世界
+        "#};
+        let expected = indoc! {r#"
+        <!-- autocorrect-disable-next-line -->
+        hello世界
+
+        hello 世界
+        "#};
+        assert_eq!(expected, format_for(raw, "markdown").out);
+        assert_eq!(1, lint_for(raw, "markdown").lines.len());
+    }
 }
@@ -8,6 +8,13 @@ use pest_derive::Parser;
 #[grammar = "../grammar/gettext.pest"]
 struct GettextParser;
 
+/// Parse the keys and values of the translation file, for `i18n::check`.
+pub(crate) fn i18n_entries_gettext(text: &str) -> Result<Vec<crate::i18n::Entry>, String> {
+    GettextParser::parse(Rule::item, text)
+        .map(crate::i18n::gettext_entries)
+        .map_err(|err| err.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
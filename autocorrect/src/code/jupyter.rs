@@ -4,7 +4,9 @@ use pest::Parser as P;
 use pest_derive::Parser;
 use serde::Serialize;
 
-use crate::{FormatResult, LineResult, LintResult};
+use crate::code::to_parse_error;
+use crate::result::Results;
+use crate::{FormatResult, LineResult, LintResult, ParseError};
 
 #[derive(Parser)]
 #[grammar = "../grammar/jupyter.pest"]
@@ -41,7 +43,7 @@ pub fn format_jupyter(input: &str) -> FormatResult {
 
     let cells = parse_jupyter(input);
     if let Err(e) = cells {
-        result.error = e;
+        result.parse_error(e);
         return result;
     }
 
@@ -67,7 +69,7 @@ pub fn lint_jupyter(input: &str) -> LintResult {
 
     let cells = parse_jupyter(input);
     if let Err(e) = cells {
-        result.error = e;
+        result.parse_error(e);
         return result;
     }
 
@@ -84,6 +86,9 @@ pub fn lint_jupyter(input: &str) -> LintResult {
                             new: line.new,
                             old: line.old,
                             severity: line.severity,
+                            message: line.message,
+                            rules: line.rules,
+                            ..Default::default()
                         });
                     }
                 }
@@ -94,13 +99,13 @@ pub fn lint_jupyter(input: &str) -> LintResult {
     result
 }
 
-fn parse_jupyter(input: &str) -> Result<Vec<NotebookCell<'_>>, String> {
+fn parse_jupyter(input: &str) -> Result<Vec<NotebookCell<'_>>, ParseError> {
     // Get MarkedSource from pairs for get source when match cell_type is "markdown" in ast
     let mut cells: Vec<NotebookCell> = vec![];
 
     let ast = JupyterParser::parse(Rule::item, input);
     if let Err(e) = ast {
-        return Err(e.to_string());
+        return Err(to_parse_error(&e));
     }
 
     let pairs = ast.unwrap();
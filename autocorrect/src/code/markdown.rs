@@ -390,13 +390,14 @@ mod tests {
 
     #[test]
     fn test_disable_context_codeblock() {
-        let last_mode = *crate::config::Config::current()
+        let last_mode = crate::config::Config::current()
             .context
             .get("codeblock")
-            .unwrap();
+            .unwrap()
+            .clone();
 
         crate::config::CURRENT_CONFIG.write().unwrap().context = map! {
-            "codeblock".to_string() => SeverityMode::Off,
+            "codeblock".to_string() => SeverityMode::Off.into(),
         };
 
         let raw = indoc! {r###"
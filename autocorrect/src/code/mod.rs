@@ -1,3 +1,4 @@
+mod checked;
 #[allow(clippy::module_inception)]
 mod code;
 mod types;
@@ -33,6 +34,7 @@ mod xml;
 mod yaml;
 mod zig;
 
+pub use checked::*;
 pub use code::*;
 pub use types::*;
 
@@ -85,6 +87,10 @@ pub use yaml::*;
 /// autocorrect::lint_for(raw, "index.html");
 /// ```
 pub fn lint_for(raw: &str, filename_or_ext: &str) -> LintResult {
+    crate::rule::with_document_script(raw, || lint_for_type(raw, filename_or_ext))
+}
+
+fn lint_for_type(raw: &str, filename_or_ext: &str) -> LintResult {
     let mut result = match types::match_filename(filename_or_ext).as_str() {
         "html" => lint_html(raw),
         "yaml" => lint_yaml(raw),
@@ -144,6 +150,10 @@ pub fn lint_for(raw: &str, filename_or_ext: &str) -> LintResult {
 /// autocorrect::format_for(raw, "index.html");
 /// ```
 pub fn format_for(raw: &str, filename_or_ext: &str) -> FormatResult {
+    crate::rule::with_document_script(raw, || format_for_type(raw, filename_or_ext))
+}
+
+fn format_for_type(raw: &str, filename_or_ext: &str) -> FormatResult {
     let result = match types::match_filename(filename_or_ext).as_str() {
         "html" => format_html(raw),
         "yaml" => format_yaml(raw),
@@ -185,3 +195,19 @@ pub fn format_for(raw: &str, filename_or_ext: &str) -> FormatResult {
 
     result
 }
+
+/// Parse the keys and values of a translation file, for `i18n::check`.
+pub(crate) fn i18n_entries_for(
+    raw: &str,
+    filename_or_ext: &str,
+) -> Result<Vec<crate::i18n::Entry>, String> {
+    match types::match_filename(filename_or_ext).as_str() {
+        "yaml" => i18n_entries_yaml(raw),
+        "json" => i18n_entries_json(raw),
+        "strings" => i18n_entries_strings(raw),
+        "gettext" => i18n_entries_gettext(raw),
+        filetype => Err(format!(
+            "Unsupported file type `{filetype}`, the i18n check supports: yaml, json, strings, gettext."
+        )),
+    }
+}
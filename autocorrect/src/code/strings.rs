@@ -8,6 +8,13 @@ use pest_derive::Parser;
 #[grammar = "../grammar/strings.pest"]
 struct StringsParser;
 
+/// Parse the keys and values of the translation file, for `i18n::check`.
+pub(crate) fn i18n_entries_strings(text: &str) -> Result<Vec<crate::i18n::Entry>, String> {
+    StringsParser::parse(Rule::item, text)
+        .map(crate::i18n::strings_entries)
+        .map_err(|err| err.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
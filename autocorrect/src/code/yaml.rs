@@ -8,6 +8,13 @@ use pest_derive::Parser;
 #[grammar = "../grammar/yaml.pest"]
 struct YAMLParser;
 
+/// Parse the keys and values of the translation file, for `i18n::check`.
+pub(crate) fn i18n_entries_yaml(text: &str) -> Result<Vec<crate::i18n::Entry>, String> {
+    YAMLParser::parse(Rule::item, text)
+        .map(crate::i18n::yaml_entries)
+        .map_err(|err| err.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -46,4 +53,70 @@ mod tests {
 
         assert_eq!(expect, format_for(example, "yaml").to_string())
     }
+
+    #[test]
+    fn it_format_yaml_with_trailing_comment() {
+        let example = indoc! {r###"
+        foo: hello世界 # 注释abc
+        bar: hello世界 # autocorrect-disable-line
+        color: "#fff" # 颜色abc
+          # 缩进的注释abc
+        "###};
+
+        let expect = indoc! {r###"
+        foo: hello 世界 # 注释 abc
+        bar: hello世界 # autocorrect-disable-line
+        color: "#fff" # 颜色 abc
+          # 缩进的注释 abc
+        "###};
+
+        assert_eq!(expect, format_for(example, "yaml").to_string())
+    }
+
+    #[test]
+    fn it_format_yaml_with_hash_in_value() {
+        let example = indoc! {r###"
+        url: http://a/#x中文abc
+        color: "#fff"
+        tag: '#标签abc'
+        text: a # b中文abc
+        block: |
+          a # b中文abc
+          # 不是注释abc
+
+          c#d中文abc
+        folded: >- # 注释abc
+          多行abc
+        list:
+          - text: |
+              # 不是注释abc
+          - name: 名称abc
+        "###};
+
+        let expect = indoc! {r###"
+        url: http://a/#x中文abc
+        color: "#fff"
+        tag: '#标签 abc'
+        text: a # b 中文 abc
+        block: |
+          a # b中文abc
+          # 不是注释abc
+
+          c#d中文abc
+        folded: >- # 注释 abc
+          多行abc
+        list:
+          - text: |
+              # 不是注释abc
+          - name: 名称 abc
+        "###};
+
+        assert_eq!(expect, format_for(example, "yaml").to_string());
+
+        let lines = lint_for(example, "yaml").lines;
+        assert_eq!(
+            vec![(3, 6), (4, 8), (10, 11), (15, 11)],
+            lines.iter().map(|l| (l.line, l.col)).collect::<Vec<_>>()
+        );
+    }
 }
@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::SeverityMode;
+
+/// Config for a context, e.g.:
+///
+/// ```yml
+/// context:
+///   # Enable or disable the whole context
+///   codeblock: 1
+///   # Override the `rules` in the context
+///   string-literal:
+///     fullwidth: 0
+/// ```
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum ContextConfig {
+    /// The rule severities in the context, high priority than `rules`.
+    Rules(HashMap<String, SeverityMode>),
+    /// Enable or disable the context.
+    Mode(SeverityMode),
+}
+
+impl From<SeverityMode> for ContextConfig {
+    fn from(mode: SeverityMode) -> Self {
+        ContextConfig::Mode(mode)
+    }
+}
+
+impl ContextConfig {
+    /// The context is enabled if it is not `off`, a context with rules is always enabled.
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            ContextConfig::Mode(mode) => *mode != SeverityMode::Off,
+            ContextConfig::Rules(_) => true,
+        }
+    }
+
+    /// Get the rule severity in this context, `None` to use the `rules` config.
+    pub fn rule_severity(&self, rule_name: &str) -> Option<SeverityMode> {
+        match self {
+            ContextConfig::Rules(rules) => rules.get(rule_name).copied(),
+            ContextConfig::Mode(_) => None,
+        }
+    }
+
+    /// Merge the other config, the rules will be merged, otherwise the other one wins.
+    pub fn merge(&mut self, other: &ContextConfig) {
+        match (self, other) {
+            (ContextConfig::Rules(rules), ContextConfig::Rules(other_rules)) => {
+                for (k, v) in other_rules {
+                    rules.insert(k.to_owned(), *v);
+                }
+            }
+            (this, other) => *this = other.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_context_config() {
+        let context: HashMap<String, ContextConfig> = serde_yaml::from_str(
+            r#"
+            codeblock: 0
+            comment: "warning"
+            string-literal:
+              fullwidth: 0
+              spellcheck: error
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            context.get("codeblock"),
+            Some(&ContextConfig::Mode(SeverityMode::Off))
+        );
+        assert_eq!(
+            context.get("comment"),
+            Some(&ContextConfig::Mode(SeverityMode::Warning))
+        );
+
+        let string_literal = context.get("string-literal").unwrap();
+        assert!(string_literal.is_enabled());
+        assert_eq!(
+            string_literal.rule_severity("fullwidth"),
+            Some(SeverityMode::Off)
+        );
+        assert_eq!(
+            string_literal.rule_severity("spellcheck"),
+            Some(SeverityMode::Error)
+        );
+        assert_eq!(string_literal.rule_severity("space-word"), None);
+        assert!(!context.get("codeblock").unwrap().is_enabled());
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut context = ContextConfig::Rules(map! {
+            "fullwidth".to_owned() => SeverityMode::Off,
+            "spellcheck".to_owned() => SeverityMode::Off,
+        });
+        context.merge(&ContextConfig::Rules(map! {
+            "spellcheck".to_owned() => SeverityMode::Warning,
+        }));
+        assert_eq!(
+            context,
+            ContextConfig::Rules(map! {
+                "fullwidth".to_owned() => SeverityMode::Off,
+                "spellcheck".to_owned() => SeverityMode::Warning,
+            })
+        );
+
+        context.merge(&SeverityMode::Off.into());
+        assert_eq!(context, ContextConfig::Mode(SeverityMode::Off));
+    }
+}
@@ -1,9 +1,13 @@
+mod context;
 mod severity;
 mod spellcheck;
+mod terminology;
 pub mod toggle;
 
+pub use context::*;
 pub use severity::*;
 pub use spellcheck::*;
+pub use terminology::*;
 
 use serde::{Deserialize, Serialize};
 use std::{
@@ -14,15 +18,16 @@ use std::{
     sync::{RwLock, RwLockReadGuard},
 };
 
-use crate::serde_any;
+use crate::{rule::Context, serde_any};
 
 lazy_static! {
     static ref CONFIG_STR: &'static str = include_str!(concat!(
         env!("CARGO_MANIFEST_DIR"),
         "/.autocorrectrc.default"
     ));
-    pub(crate) static ref CURRENT_CONFIG: RwLock<Config> =
-        RwLock::new(Config::from_str(&CONFIG_STR).unwrap());
+    /// The built-in default config, for the default severity of the rules.
+    pub(crate) static ref DEFAULT_CONFIG: Config = Config::from_str(&CONFIG_STR).unwrap();
+    pub(crate) static ref CURRENT_CONFIG: RwLock<Config> = RwLock::new(DEFAULT_CONFIG.clone());
 }
 
 pub trait ConfigFileTypes {
@@ -53,6 +58,8 @@ pub struct Config {
     #[serde(default)]
     pub spellcheck: SpellcheckConfig,
     #[serde(default)]
+    pub terminology: TerminologyConfig,
+    #[serde(default)]
     pub rules: HashMap<String, SeverityMode>,
     // Special text to ignore
     #[serde(default)]
@@ -60,8 +67,29 @@ pub struct Config {
     // Addition file types map, high priority than default
     #[serde(default)]
     pub file_types: HashMap<String, String>,
+    /// Enable or disable in the context, or the rules in the context, e.g.: `codeblock: 1`, `comment: { fullwidth: 0 }`
+    #[serde(default)]
+    pub context: HashMap<String, ContextConfig>,
+    /// The locale of the content, e.g.: `zh-CN`, `zh-TW`
+    #[serde(default)]
+    pub locale: Option<String>,
+    /// How to split the text for the rules, default is `chunk`
+    #[serde(default)]
+    pub pipeline: Option<Pipeline>,
+    /// Report the disable directives that suppress nothing, have unknown rules, or are not enabled again.
     #[serde(default)]
-    pub context: HashMap<String, SeverityMode>,
+    pub report_unused_disable_directives: Option<bool>,
+}
+
+/// How to split the text for the rules.
+#[derive(Deserialize, Serialize, Default, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Pipeline {
+    /// Split the text by spaces, the rules see a chunk at a time.
+    #[default]
+    Chunk,
+    /// Tokenize the text as a sentence, the rules see across the words, only the paths and URLs are kept.
+    Sentence,
 }
 
 pub fn load_file<P>(config_file: P) -> Result<Config, Error>
@@ -142,6 +170,8 @@ impl Config {
             Ok(config) => config,
             Err(err) => return Err(format!("Config::from_str parse error: {err:?}").into()),
         };
+        config.spellcheck.validate()?;
+        config.terminology.validate()?;
 
         config.prepare();
 
@@ -150,6 +180,7 @@ impl Config {
 
     pub fn prepare(&mut self) {
         self.spellcheck.prepare();
+        self.terminology.prepare(self.locale.as_deref());
     }
 
     pub fn merge(&mut self, config: &Config) -> Result<Config, Error> {
@@ -164,9 +195,15 @@ impl Config {
             self.rules.insert("spellcheck".to_string(), mode);
         }
 
-        config.context.iter().for_each(|(k, v)| {
-            self.context.insert(k.to_owned(), v.to_owned());
-        });
+        config
+            .context
+            .iter()
+            .for_each(|(k, v)| match self.context.get_mut(k) {
+                Some(context) => context.merge(v),
+                None => {
+                    self.context.insert(k.to_owned(), v.to_owned());
+                }
+            });
 
         config.text_rules.iter().for_each(|(k, v)| {
             self.text_rules.insert(k.to_owned(), v.to_owned());
@@ -176,6 +213,37 @@ impl Config {
             self.file_types.insert(k.to_owned(), v.to_owned());
         });
 
+        if config.locale.is_some() {
+            self.locale.clone_from(&config.locale);
+        }
+
+        if config.pipeline.is_some() {
+            self.pipeline = config.pipeline;
+        }
+
+        if config.report_unused_disable_directives.is_some() {
+            self.report_unused_disable_directives = config.report_unused_disable_directives;
+        }
+
+        for pack in config.terminology.packs.iter() {
+            if !self.terminology.packs.contains(pack) {
+                self.terminology.packs.push(pack.clone());
+            }
+        }
+        config.terminology.words.iter().for_each(|(k, v)| {
+            self.terminology
+                .words
+                .entry(k.to_owned())
+                .or_default()
+                .extend(v.iter().cloned());
+        });
+
+        for name in config.spellcheck.dictionaries.iter() {
+            if !self.spellcheck.dictionaries.contains(name) {
+                self.spellcheck.dictionaries.push(name.clone());
+            }
+        }
+
         self.spellcheck.words = self
             .spellcheck
             .words
@@ -191,12 +259,39 @@ impl Config {
 
     /// Check is enable format in context
     pub fn is_enabled_context(&self, name: &str) -> bool {
-        if let Some(mode) = self.context.get(name) {
-            return *mode != SeverityMode::Off;
+        if let Some(context) = self.context.get(name) {
+            return context.is_enabled();
         }
 
         false
     }
+
+    /// Check is the text context disabled, the context is enabled by default.
+    pub(crate) fn is_disabled_text_context(&self, context: Context) -> bool {
+        self.context
+            .get(context.name())
+            .map(|c| !c.is_enabled())
+            .unwrap_or(false)
+    }
+
+    /// Get the rule severity in the text context, the `context` config has higher priority than `rules`.
+    ///
+    /// Returns `None` if the rule is not in the config.
+    pub(crate) fn get_rule_severity(
+        &self,
+        rule_name: &str,
+        context: Context,
+    ) -> Option<SeverityMode> {
+        if let Some(severity) = self
+            .context
+            .get(context.name())
+            .and_then(|c| c.rule_severity(rule_name))
+        {
+            return Some(severity);
+        }
+
+        self.rules.get(rule_name).copied()
+    }
 }
 
 // Setup config for test for load tests/.autocorrectrc.test
@@ -253,7 +348,10 @@ mod tests {
         assert_eq!(Some(&SeverityMode::Warning), config.rules.get("dar"));
 
         assert_eq!(Some(SeverityMode::Off), config.spellcheck.mode);
-        assert_eq!(vec!["Foo", "Bar"], config.spellcheck.words);
+        assert_eq!(
+            vec![SpellcheckWord::from("Foo"), "Bar".into()],
+            config.spellcheck.words
+        );
         assert_eq!(Some(&SeverityMode::Error), config.rules.get("foo"));
         assert_eq!(Some(&SeverityMode::Off), config.rules.get("bar"));
         assert_eq!(Some(&SeverityMode::Warning), config.rules.get("dar"));
@@ -290,7 +388,7 @@ mod tests {
 
         config = Config::from_str(r#"{ "spellcheck": { "words" : ["Hello"] } }"#).unwrap();
         assert_eq!(None, config.spellcheck.mode);
-        assert_eq!(vec!["Hello"], config.spellcheck.words);
+        assert_eq!(vec![SpellcheckWord::from("Hello")], config.spellcheck.words);
     }
 
     #[test]
@@ -313,6 +411,14 @@ mod tests {
             - Bar
         fileTypes:
           Foo: foo
+        locale: zh-TW
+        pipeline: sentence
+        terminology:
+          packs:
+            - it
+          words:
+            zh-TW:
+              - 幻灯片 = 簡報
         "#};
 
         config = Config::from_str(yaml_str).unwrap();
@@ -325,13 +431,33 @@ mod tests {
         assert_eq!(Some(&SeverityMode::Off), config.text_rules.get("word"));
 
         assert_eq!(Some(SeverityMode::Error), config.spellcheck.mode);
-        assert_eq!(vec!["Foo", "Bar"], config.spellcheck.words);
+        assert_eq!(
+            vec![SpellcheckWord::from("Foo"), "Bar".into()],
+            config.spellcheck.words
+        );
 
         assert_eq!(Some(&"foo".to_owned()), config.file_types.get("Foo"));
+        assert_eq!(Some("zh-TW".to_owned()), config.locale);
+        assert_eq!(Some(Pipeline::Sentence), config.pipeline);
+        assert_eq!(vec!["it"], config.terminology.packs);
+        assert_eq!(
+            Some(&"簡報".to_owned()),
+            config.terminology.word_map.get("幻灯片")
+        );
+        assert_eq!(
+            Some(&"資訊".to_owned()),
+            config.terminology.word_map.get("信息")
+        );
 
         config = Config::from_str("").unwrap();
         assert_eq!(None, config.spellcheck.mode);
-        assert_eq!(Vec::<String>::new(), config.spellcheck.words);
+        assert_eq!(Vec::<SpellcheckWord>::new(), config.spellcheck.words);
+
+        let err = Config::from_str("terminology:\n  packs: [it, foo]").unwrap_err();
+        assert_eq!(
+            "Unknown terminology pack `foo`, available: it",
+            err.to_string()
+        );
     }
 
     #[test]
@@ -349,6 +475,10 @@ mod tests {
                 "spellcheck" => assert_eq!(SeverityMode::Warning, v),
                 "space-dash" => assert_eq!(SeverityMode::Error, v),
                 "space-dollar" => assert_eq!(SeverityMode::Off, v),
+                "script-consistency" => assert_eq!(SeverityMode::Off, v),
+                "heading-space" => assert_eq!(SeverityMode::Off, v),
+                "heading-punctuation" => assert_eq!(SeverityMode::Off, v),
+                "normalize-whitespace" => assert_eq!(SeverityMode::Off, v),
                 _ => assert_eq!(SeverityMode::Error, v),
             }
         }
@@ -365,8 +495,11 @@ mod tests {
                 "foo".to_owned() => SeverityMode::Error,
             },
             context: map! {
-                "foo".to_owned() => SeverityMode::Error,
-                "foo1".to_owned() => SeverityMode::Off,
+                "foo".to_owned() => SeverityMode::Error.into(),
+                "foo1".to_owned() => SeverityMode::Off.into(),
+                "comment".to_owned() => ContextConfig::Rules(map! {
+                    "fullwidth".to_owned() => SeverityMode::Off,
+                }),
             },
             text_rules: map! {
                 "a".to_owned() => SeverityMode::Off,
@@ -378,9 +511,20 @@ mod tests {
             },
             spellcheck: SpellcheckConfig {
                 mode: Some(SeverityMode::Warning),
-                words: vec!["foo".to_string(), "bar".to_string(), "baz".to_string()],
+                dictionaries: vec!["apple".to_string()],
+                words: vec!["foo".into(), "bar".into(), "baz".into()],
+                ..Default::default()
+            },
+            terminology: TerminologyConfig {
+                packs: vec!["it".to_string()],
+                words: map! {
+                    "zh-TW".to_owned() => vec!["信息 = 資訊".to_string()],
+                },
                 ..Default::default()
             },
+            locale: Some("zh-CN".to_owned()),
+            pipeline: None,
+            report_unused_disable_directives: None,
         };
 
         let config1 = Config {
@@ -388,8 +532,11 @@ mod tests {
                 "bar".to_owned() => SeverityMode::Warning,
             },
             context: map! {
-                "foo".to_owned() => SeverityMode::Warning,
-                "foo2".to_owned() => SeverityMode::Off,
+                "foo".to_owned() => SeverityMode::Warning.into(),
+                "foo2".to_owned() => SeverityMode::Off.into(),
+                "comment".to_owned() => ContextConfig::Rules(map! {
+                    "spellcheck".to_owned() => SeverityMode::Warning,
+                }),
             },
             text_rules: map! {
                 "world".to_owned() => SeverityMode::Off
@@ -400,9 +547,21 @@ mod tests {
             },
             spellcheck: SpellcheckConfig {
                 mode: Some(SeverityMode::Off),
-                words: vec!["foo1".to_string(), "bar1".to_string()],
+                dictionaries: vec!["cloud".to_string(), "apple".to_string()],
+                words: vec!["foo1".into(), "bar1".into()],
+                ..Default::default()
+            },
+            terminology: TerminologyConfig {
+                packs: vec!["it".to_string(), "foo".to_string()],
+                words: map! {
+                    "zh-TW".to_owned() => vec!["视频 = 視訊".to_string()],
+                    "zh-CN".to_owned() => vec!["資訊 = 信息".to_string()],
+                },
                 ..Default::default()
             },
+            locale: Some("zh-TW".to_owned()),
+            pipeline: Some(Pipeline::Sentence),
+            report_unused_disable_directives: Some(true),
         };
 
         config.merge(&config1).unwrap();
@@ -414,9 +573,24 @@ mod tests {
         };
         assert_eq!(new_rules, config.rules);
 
-        assert_eq!(config.context.get("foo"), Some(&SeverityMode::Warning));
-        assert_eq!(config.context.get("foo1"), Some(&SeverityMode::Off));
-        assert_eq!(config.context.get("foo2"), Some(&SeverityMode::Off));
+        assert_eq!(
+            config.context.get("foo"),
+            Some(&SeverityMode::Warning.into())
+        );
+        assert_eq!(config.context.get("foo1"), Some(&SeverityMode::Off.into()));
+        assert_eq!(config.context.get("foo2"), Some(&SeverityMode::Off.into()));
+        assert_eq!(
+            config.get_rule_severity("fullwidth", Context::Comment),
+            Some(SeverityMode::Off)
+        );
+        assert_eq!(
+            config.get_rule_severity("spellcheck", Context::Comment),
+            Some(SeverityMode::Warning)
+        );
+        assert_eq!(
+            config.get_rule_severity("spellcheck", Context::Prose),
+            Some(SeverityMode::Off)
+        );
 
         let new_text_rules = map! {
             "a".to_owned() => SeverityMode::Off,
@@ -432,15 +606,36 @@ mod tests {
         };
         assert_eq!(new_file_types, config.file_types);
 
+        assert_eq!(config.locale, Some("zh-TW".to_owned()));
+        assert_eq!(config.pipeline, Some(Pipeline::Sentence));
+        assert_eq!(config.report_unused_disable_directives, Some(true));
+
+        assert_eq!(
+            config.terminology.packs,
+            vec!["it".to_string(), "foo".to_string()]
+        );
+        assert_eq!(
+            config.terminology.words.get("zh-TW"),
+            Some(&vec!["信息 = 資訊".to_string(), "视频 = 視訊".to_string()])
+        );
+        assert_eq!(
+            config.terminology.words.get("zh-CN"),
+            Some(&vec!["資訊 = 信息".to_string()])
+        );
+
         assert_eq!(config.spellcheck.mode, Some(SeverityMode::Off));
+        assert_eq!(
+            config.spellcheck.dictionaries,
+            vec!["apple".to_string(), "cloud".to_string()]
+        );
         assert_eq!(
             config.spellcheck.words,
             vec![
-                "foo".to_string(),
-                "bar".to_string(),
-                "baz".to_string(),
-                "foo1".to_string(),
-                "bar1".to_string()
+                SpellcheckWord::from("foo"),
+                "bar".into(),
+                "baz".into(),
+                "foo1".into(),
+                "bar1".into()
             ]
         );
     }
@@ -1,11 +1,19 @@
 use crate::keyword;
 
 use super::severity::*;
+use regex::{Regex, RegexSet};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 lazy_static! {
     pub static ref PAIR_RE: regex::Regex = regex::Regex::new(r"\s*=\s*").unwrap();
+    /// Built-in dictionaries, enable by `spellcheck.dictionaries`.
+    static ref BUILTIN_DICTIONARIES: Vec<(&'static str, &'static str)> = vec![
+        ("apple", include_str!("../../dict/spellcheck/apple.txt")),
+        ("cloud", include_str!("../../dict/spellcheck/cloud.txt")),
+        ("databases", include_str!("../../dict/spellcheck/databases.txt")),
+        ("programming", include_str!("../../dict/spellcheck/programming.txt")),
+    ];
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, Default)]
@@ -13,74 +21,410 @@ pub struct SpellcheckConfig {
     #[serde(default)]
     /// DEPRECATED: use `rules.spellcheck` instead
     pub mode: Option<SeverityMode>,
+    /// Built-in dictionaries to enable, e.g.: `programming`, `apple`
+    #[serde(default)]
+    pub dictionaries: Vec<String>,
     #[serde(default)]
     pub words: Vec<SpellcheckWord>,
+    /// The case insensitive plain words,
     /// key is always in lowercase
-    /// value is the original word
     #[serde(skip)]
-    pub word_map: HashMap<String, String>,
+    pub word_map: HashMap<String, SpellcheckEntry>,
     /// A tree to match words
     #[serde(skip)]
     pub matcher: keyword::Node,
+    /// The case sensitive plain words, key is the original word
+    #[serde(skip)]
+    pub case_sensitive_word_map: HashMap<String, SpellcheckEntry>,
+    /// A tree to match case sensitive words
+    #[serde(skip)]
+    pub case_sensitive_matcher: keyword::Node,
+    /// A set to quickly check which regex words are matched
+    #[serde(skip)]
+    pub regex_set: RegexSet,
+    /// The regex words, in the same order of `regex_set`
+    #[serde(skip)]
+    pub regex_words: Vec<(Regex, SpellcheckEntry)>,
 }
 
-impl SpellcheckConfig {
-    pub fn prepare(&mut self) {
-        self.matcher = keyword::Node::new(true);
-        self.matcher.add_keywords(
-            self.words
-                .iter()
-                .map(|w| {
-                    // get the = before, and trim
-                    // ios
-                    // wifi = Wi-Fi
-                    w.split('=').next().unwrap().trim()
-                })
-                .collect::<Vec<_>>(),
-        );
-        self.matcher.build();
+/// A word in `spellcheck.words`.
+///
+/// ```yml
+/// words:
+///   # Short form
+///   - iOS
+///   - wifi = Wi-Fi
+///   # Structured form
+///   - from: i[Oo][Ss]
+///     to: iOS
+///     regex: true
+///     caseSensitive: true
+/// ```
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum SpellcheckWord {
+    Plain(String),
+    Entry(SpellcheckEntry),
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SpellcheckEntry {
+    /// The word (or regex pattern) to match.
+    pub from: String,
+    /// The correct word, default is same as `from`.
+    /// For regex, the `$1` / `${name}` will be replaced by the captured group.
+    #[serde(default)]
+    pub to: String,
+    #[serde(default)]
+    pub regex: bool,
+    #[serde(default)]
+    pub case_sensitive: bool,
+    /// Only match the whole word, default: true
+    #[serde(default = "default_whole_word")]
+    pub whole_word: bool,
+    /// Override the severity of `rules.spellcheck` for this word.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<SeverityMode>,
+}
+
+fn default_whole_word() -> bool {
+    true
+}
+
+impl From<&str> for SpellcheckWord {
+    fn from(s: &str) -> Self {
+        SpellcheckWord::Plain(s.to_string())
+    }
+}
 
-        if !self.words.is_empty() {
-            let mut lines = self.words.clone();
+impl From<String> for SpellcheckWord {
+    fn from(s: String) -> Self {
+        SpellcheckWord::Plain(s)
+    }
+}
 
-            // Sort: Longger first, then alphabetically.
-            lines.sort_by(|a, b| {
-                let mut a = a.as_str();
-                let mut b = b.as_str();
+impl SpellcheckWord {
+    /// Convert to entry, the short form `wifi = Wi-Fi` will be split into `from` and `to`.
+    pub fn to_entry(&self) -> SpellcheckEntry {
+        match self {
+            SpellcheckWord::Plain(line) => {
+                let pair = PAIR_RE.split(line).collect::<Vec<_>>();
+                let (from, to) = if pair.len() == 2 {
+                    (pair[0].trim(), pair[1].trim())
+                } else {
+                    (line.trim(), line.trim())
+                };
 
-                let pair_a = PAIR_RE.split(a).collect::<Vec<_>>();
-                if pair_a.len() == 2 {
-                    a = pair_a[0];
+                SpellcheckEntry {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                    regex: false,
+                    case_sensitive: false,
+                    whole_word: true,
+                    severity: None,
                 }
-                let pair_b = PAIR_RE.split(b).collect::<Vec<_>>();
-                if pair_b.len() == 2 {
-                    b = pair_b[0];
+            }
+            SpellcheckWord::Entry(entry) => {
+                let mut entry = entry.clone();
+                if entry.to.is_empty() {
+                    entry.to.clone_from(&entry.from);
                 }
-                a = a.trim();
-                b = b.trim();
+                entry
+            }
+        }
+    }
+}
+
+impl SpellcheckEntry {
+    /// Build regex with inline flags, so the `RegexSet` has the same behavior.
+    fn build_regex(&self) -> Result<Regex, regex::Error> {
+        if self.case_sensitive {
+            Regex::new(&self.from)
+        } else {
+            Regex::new(&format!("(?i){}", self.from))
+        }
+    }
+}
+
+impl SpellcheckConfig {
+    pub fn prepare(&mut self) {
+        self.word_map.clear();
+        self.case_sensitive_word_map.clear();
+        self.regex_words.clear();
 
-                b.len().cmp(&a.len()).then(a.cmp(b))
-            });
+        let dictionary_words = self
+            .dictionaries
+            .iter()
+            .filter_map(|name| builtin_dictionary(name))
+            .flat_map(|dict| dict.words())
+            .collect::<Vec<_>>();
 
-            for l in lines.iter().filter(|l| !l.trim().is_empty()) {
-                let mut left_str = l.as_str();
-                let mut right_str = l.as_str();
+        // The later words will override the former, so the user's words have higher priority.
+        for entry in dictionary_words
+            .iter()
+            .chain(self.words.iter())
+            .map(|w| w.to_entry())
+        {
+            if entry.from.is_empty() {
+                continue;
+            }
+
+            // `severity: off` to disable a word, e.g.: a word in the default config.
+            if entry.severity == Some(SeverityMode::Off) {
+                self.word_map.remove(&entry.from.to_lowercase());
+                self.case_sensitive_word_map.remove(&entry.from);
+                self.regex_words.retain(|(_, e)| e.from != entry.from);
+                continue;
+            }
 
-                let pair = PAIR_RE.split(l).collect::<Vec<_>>();
-                if pair.len() == 2 {
-                    left_str = pair[0];
-                    right_str = pair[1];
+            if entry.regex {
+                // The invalid regex are reported by `validate`.
+                if let Ok(re) = entry.build_regex() {
+                    self.regex_words.push((re, entry));
                 }
+            } else if entry.case_sensitive {
+                self.case_sensitive_word_map
+                    .insert(entry.from.clone(), entry);
+            } else {
+                self.word_map.insert(entry.from.to_lowercase(), entry);
+            }
+        }
 
-                left_str = left_str.trim();
-                right_str = right_str.trim();
+        self.matcher = keyword::Node::new(true);
+        self.matcher.add_keywords(self.word_map.keys());
+        self.matcher.build();
+
+        self.case_sensitive_matcher = keyword::Node::new(false);
+        self.case_sensitive_matcher
+            .add_keywords(self.case_sensitive_word_map.keys());
+        self.case_sensitive_matcher.build();
+
+        self.regex_set =
+            RegexSet::new(self.regex_words.iter().map(|(re, _)| re.as_str())).unwrap_or_default();
+    }
 
-                let key = left_str.to_lowercase();
+    /// Check the dictionaries are exist and the regex words are valid
+    pub fn validate(&self) -> Result<(), String> {
+        for name in self.dictionaries.iter() {
+            if builtin_dictionary(name).is_none() {
+                return Err(format!(
+                    "Unknown spellcheck dictionary `{}`, available: {}",
+                    name,
+                    builtin_dictionaries()
+                        .iter()
+                        .map(|d| d.name)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+        }
 
-                self.word_map.insert(key.clone(), right_str.to_string());
+        for entry in self.words.iter().map(|w| w.to_entry()) {
+            if !entry.regex {
+                continue;
+            }
+
+            if let Err(err) = entry.build_regex() {
+                return Err(format!(
+                    "Invalid regex in spellcheck words `{}`: {}",
+                    entry.from, err
+                ));
             }
         }
+
+        Ok(())
     }
 }
 
-type SpellcheckWord = String;
+/// A built-in spellcheck dictionary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dictionary {
+    pub name: &'static str,
+    data: &'static str,
+}
+
+impl Dictionary {
+    /// The first comment line of the dictionary
+    pub fn description(&self) -> &'static str {
+        self.data
+            .lines()
+            .next()
+            .and_then(|line| line.strip_prefix('#'))
+            .unwrap_or_default()
+            .trim()
+    }
+
+    /// The words in the dictionary, in short form.
+    pub fn words(self) -> impl Iterator<Item = SpellcheckWord> {
+        self.data
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(SpellcheckWord::from)
+    }
+}
+
+/// Get all built-in spellcheck dictionaries.
+pub fn builtin_dictionaries() -> Vec<Dictionary> {
+    BUILTIN_DICTIONARIES
+        .iter()
+        .map(|(name, data)| Dictionary { name, data })
+        .collect()
+}
+
+/// Get a built-in spellcheck dictionary by name.
+pub fn builtin_dictionary(name: &str) -> Option<Dictionary> {
+    builtin_dictionaries()
+        .into_iter()
+        .find(|d| d.name.eq_ignore_ascii_case(name.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_parse_words() {
+        let config: SpellcheckConfig = serde_yaml::from_str(indoc! {r#"
+        words:
+          - iOS
+          - wifi = Wi-Fi
+          - from: GitHub
+            caseSensitive: true
+          - from: i[Oo][Ss]
+            to: iOS
+            regex: true
+            caseSensitive: true
+            wholeWord: false
+            severity: warning
+        "#})
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                SpellcheckWord::from("iOS"),
+                SpellcheckWord::from("wifi = Wi-Fi"),
+                SpellcheckWord::Entry(SpellcheckEntry {
+                    from: "GitHub".to_owned(),
+                    to: "".to_owned(),
+                    regex: false,
+                    case_sensitive: true,
+                    whole_word: true,
+                    severity: None,
+                }),
+                SpellcheckWord::Entry(SpellcheckEntry {
+                    from: "i[Oo][Ss]".to_owned(),
+                    to: "iOS".to_owned(),
+                    regex: true,
+                    case_sensitive: true,
+                    whole_word: false,
+                    severity: Some(SeverityMode::Warning),
+                }),
+            ],
+            config.words
+        );
+    }
+
+    #[test]
+    fn test_prepare() {
+        let mut config = SpellcheckConfig {
+            words: vec![
+                "iOS".into(),
+                "ipad = iPad".into(),
+                "wifi = Wi-Fi".into(),
+                SpellcheckWord::Entry(SpellcheckEntry {
+                    from: "GitHub".to_owned(),
+                    to: "".to_owned(),
+                    regex: false,
+                    case_sensitive: true,
+                    whole_word: true,
+                    severity: None,
+                }),
+                SpellcheckWord::Entry(SpellcheckEntry {
+                    from: r"mac\s?os".to_owned(),
+                    to: "macOS".to_owned(),
+                    regex: true,
+                    case_sensitive: false,
+                    whole_word: true,
+                    severity: None,
+                }),
+                SpellcheckWord::Entry(SpellcheckEntry {
+                    from: "ipad".to_owned(),
+                    to: "iPad".to_owned(),
+                    regex: false,
+                    case_sensitive: false,
+                    whole_word: true,
+                    severity: Some(SeverityMode::Off),
+                }),
+            ],
+            ..Default::default()
+        };
+        config.prepare();
+
+        assert_eq!("iOS", config.word_map.get("ios").unwrap().to);
+        assert_eq!("Wi-Fi", config.word_map.get("wifi").unwrap().to);
+        assert_eq!(None, config.word_map.get("ipad"));
+        assert_eq!(
+            "GitHub",
+            config.case_sensitive_word_map.get("GitHub").unwrap().to
+        );
+        assert_eq!(1, config.regex_words.len());
+        assert!(config.regex_set.is_match("MacOS"));
+        assert!(config.validate().is_ok());
+
+        config.words.push(SpellcheckWord::Entry(SpellcheckEntry {
+            from: "(foo".to_owned(),
+            to: "".to_owned(),
+            regex: true,
+            case_sensitive: false,
+            whole_word: true,
+            severity: None,
+        }));
+        config.prepare();
+        assert_eq!(1, config.regex_words.len());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_builtin_dictionaries() {
+        let names = builtin_dictionaries()
+            .iter()
+            .map(|d| d.name)
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["apple", "cloud", "databases", "programming"], names);
+
+        let dict = builtin_dictionary("Apple").unwrap();
+        assert_eq!("Apple products and platforms.", dict.description());
+        assert!(dict.words().any(|w| w == SpellcheckWord::from("macOS")));
+        assert!(dict
+            .words()
+            .all(|w| !matches!(w, SpellcheckWord::Plain(ref s) if s.starts_with('#'))));
+
+        assert_eq!(None, builtin_dictionary("foo"));
+    }
+
+    #[test]
+    fn test_prepare_with_dictionaries() {
+        let mut config = SpellcheckConfig {
+            dictionaries: vec!["programming".to_owned(), "apple".to_owned()],
+            words: vec!["javascript = JavaScript".into(), "ios = IOS".into()],
+            ..Default::default()
+        };
+        config.prepare();
+        assert!(config.validate().is_ok());
+
+        assert_eq!("TypeScript", config.word_map.get("typescript").unwrap().to);
+        assert_eq!("macOS", config.word_map.get("macos").unwrap().to);
+        // User's words have higher priority
+        assert_eq!("IOS", config.word_map.get("ios").unwrap().to);
+        assert_eq!(None, config.word_map.get("postgresql"));
+
+        config.dictionaries.push("foo".to_owned());
+        assert_eq!(
+            Err("Unknown spellcheck dictionary `foo`, available: apple, cloud, databases, programming".to_owned()),
+            config.validate()
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use crate::keyword;
+use crate::rule::Script;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::PAIR_RE;
+
+lazy_static! {
+    /// Built-in terminology packs, key is `{locale}/{name}`.
+    static ref BUILTIN_PACKS: HashMap<&'static str, &'static str> = map! {
+        "zh-CN/it" => include_str!("../../dict/terminology/zh-CN/it.txt"),
+        "zh-TW/it" => include_str!("../../dict/terminology/zh-TW/it.txt"),
+    };
+}
+
+/// Regional terminology, for example `信息` should be `資訊` in zh-TW.
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct TerminologyConfig {
+    /// Built-in packs to enable, e.g.: `it`
+    #[serde(default)]
+    pub packs: Vec<String>,
+    /// Terms for each locale, e.g.: `zh-TW: ["信息 = 資訊"]`
+    #[serde(default)]
+    pub words: HashMap<String, Vec<TerminologyWord>>,
+    /// key is the wrong term, value is the preferred term, for current locale.
+    #[serde(skip)]
+    pub word_map: HashMap<String, String>,
+    /// A tree to match terms
+    #[serde(skip)]
+    pub matcher: keyword::Node,
+}
+
+impl TerminologyConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        let names = builtin_pack_names();
+        for name in self.packs.iter() {
+            if !names.iter().any(|n| n.eq_ignore_ascii_case(name)) {
+                return Err(format!(
+                    "Unknown terminology pack `{}`, available: {}",
+                    name,
+                    names.join(", ")
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn prepare(&mut self, locale: Option<&str>) {
+        self.word_map.clear();
+
+        if let Some(locale) = locale {
+            let pack_lines = self
+                .packs
+                .iter()
+                .filter_map(|name| builtin_pack(locale, name))
+                .flat_map(|data| data.lines());
+            let user_lines = self
+                .words
+                .iter()
+                .filter(|(k, _)| k.eq_ignore_ascii_case(locale))
+                .flat_map(|(_, words)| words.iter().map(|w| w.as_str()));
+
+            // User's terms after packs, so they can override the packs.
+            for line in pack_lines.chain(user_lines) {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+
+                let pair = PAIR_RE.split(line).collect::<Vec<_>>();
+                if pair.len() != 2 {
+                    continue;
+                }
+
+                let (from, to) = (pair[0].trim(), pair[1].trim());
+                if from.is_empty() || to.is_empty() || from == to {
+                    continue;
+                }
+
+                self.word_map.insert(from.to_string(), to.to_string());
+            }
+        }
+
+        self.matcher = keyword::Node::new(false);
+        self.matcher.add_keywords(self.word_map.keys());
+        self.matcher.build();
+    }
+}
+
+/// Get a built-in terminology pack by locale and name.
+///
+/// The locale is normalized like the script detection, e.g.: `zh-HK`, `zh-Hant`, `zh_TW` use the `zh-TW` packs.
+pub fn builtin_pack(locale: &str, name: &str) -> Option<&'static str> {
+    let locale = match Script::from_locale(locale)? {
+        Script::Simplified => "zh-CN",
+        Script::Traditional => "zh-TW",
+    };
+
+    BUILTIN_PACKS
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(&format!("{locale}/{name}")))
+        .map(|(_, data)| *data)
+}
+
+/// The names of the built-in terminology packs, e.g.: `it`
+pub fn builtin_pack_names() -> Vec<&'static str> {
+    let mut names = BUILTIN_PACKS
+        .keys()
+        .filter_map(|key| key.split('/').nth(1))
+        .collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+    names
+}
+
+type TerminologyWord = String;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prepare() {
+        let mut config = TerminologyConfig {
+            packs: vec!["it".to_string()],
+            words: map! {
+                "zh-TW".to_string() => vec!["视频 = 視訊".to_string(), "幻灯片 = 簡報".to_string()],
+                "zh-CN".to_string() => vec!["簡報 = 幻灯片".to_string()],
+            },
+            ..Default::default()
+        };
+
+        config.prepare(Some("zh-tw"));
+        assert_eq!(Some(&"預設".to_string()), config.word_map.get("默认"));
+        assert_eq!(Some(&"資訊".to_string()), config.word_map.get("信息"));
+        // User's terms override the packs
+        assert_eq!(Some(&"視訊".to_string()), config.word_map.get("视频"));
+        assert_eq!(Some(&"簡報".to_string()), config.word_map.get("幻灯片"));
+        assert_eq!(None, config.word_map.get("簡報"));
+
+        config.prepare(Some("zh-CN"));
+        assert_eq!(Some(&"默认".to_string()), config.word_map.get("預設"));
+        assert_eq!(Some(&"幻灯片".to_string()), config.word_map.get("簡報"));
+        assert_eq!(None, config.word_map.get("信息"));
+
+        // The packs of the other Traditional Chinese locales, the words are only for `zh-TW`
+        config.prepare(Some("zh-HK"));
+        assert_eq!(Some(&"資訊".to_string()), config.word_map.get("信息"));
+        assert_eq!(Some(&"影片".to_string()), config.word_map.get("视频"));
+
+        config.prepare(None);
+        assert!(config.word_map.is_empty());
+    }
+
+    #[test]
+    fn test_builtin_pack() {
+        assert!(builtin_pack("zh-TW", "it").is_some());
+        assert!(builtin_pack("zh-tw", "IT").is_some());
+        assert!(builtin_pack("zh-CN", "it").is_some());
+        assert!(builtin_pack("ja", "it").is_none());
+
+        for locale in ["zh-HK", "zh-Hant", "zh_TW", "zh-Hant-TW"] {
+            assert_eq!(builtin_pack("zh-TW", "it"), builtin_pack(locale, "it"));
+        }
+        for locale in ["zh", "zh-Hans", "zh_CN", "zh-SG"] {
+            assert_eq!(builtin_pack("zh-CN", "it"), builtin_pack(locale, "it"));
+        }
+        assert!(builtin_pack("zh-HK", "not-exist").is_none());
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut config = TerminologyConfig {
+            packs: vec!["it".to_string(), "IT".to_string()],
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config.packs.push("not-exist".to_string());
+        assert_eq!(
+            Err("Unknown terminology pack `not-exist`, available: it".to_string()),
+            config.validate()
+        );
+    }
+}
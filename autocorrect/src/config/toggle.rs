@@ -16,6 +16,126 @@ pub enum Toggle {
     Enable(Arc<HashMap<String, bool>>),
 }
 
+/// The line scope of a line toggle.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum LineScope {
+    /// `autocorrect-disable-line`, the line of the comment.
+    Line,
+    /// `autocorrect-disable-next-line`, the line after the comment.
+    NextLine,
+}
+
+/// The toggles only for a single line, by the line number.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct LineToggles(HashMap<usize, Toggle>);
+
+impl LineToggles {
+    /// Add a toggle for the line, merge with the existing toggle of the line.
+    pub fn insert(&mut self, line: usize, toggle: Toggle) {
+        self.0
+            .entry(line)
+            .and_modify(|t| t.merge(toggle.clone()))
+            .or_insert(toggle);
+    }
+
+    pub fn get(&self, line: usize) -> Option<&Toggle> {
+        self.0.get(&line)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// The kind of a directive, see `Directive`.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum DirectiveKind {
+    /// `autocorrect: false`, disable the whole file
+    File,
+    /// `autocorrect-disable`, disable until `autocorrect-enable`
+    Region,
+    /// `autocorrect-enable`
+    Enable,
+    /// `autocorrect-disable-line` or `autocorrect-disable-next-line`, disable the line
+    Line(usize),
+}
+
+/// A toggle directive in the comment, for the `reportUnusedDisableDirectives` config.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Directive {
+    pub line: usize,
+    pub col: usize,
+    /// The first line of the comment, e.g.: `// autocorrect-disable space-word`
+    pub text: String,
+    pub kind: DirectiveKind,
+    pub toggle: Toggle,
+    /// Some lint results are suppressed by the directive
+    pub used: bool,
+    /// The region is enabled again, always `true` except `File` and `Region`
+    pub closed: bool,
+}
+
+impl Directive {
+    pub fn new(
+        kind: DirectiveKind,
+        toggle: Toggle,
+        text: &str,
+        (line, col): (usize, usize),
+    ) -> Self {
+        Directive {
+            line,
+            col,
+            text: text.lines().next().unwrap_or_default().trim().to_string(),
+            kind,
+            toggle,
+            used: false,
+            closed: !matches!(kind, DirectiveKind::File | DirectiveKind::Region),
+        }
+    }
+
+    /// Is the directive applied to the line now, the region is applied until it is closed.
+    pub fn is_applied(&self, line: usize) -> bool {
+        match self.kind {
+            DirectiveKind::Line(target) => target == line,
+            DirectiveKind::File | DirectiveKind::Region => !self.closed,
+            DirectiveKind::Enable => false,
+        }
+    }
+
+    /// Does the directive suppress the changes of the rules, empty disable all rules.
+    pub fn is_suppressed(&self, rule_names: &[String]) -> bool {
+        match &self.toggle {
+            Toggle::Disable(rules) => {
+                !rule_names.is_empty()
+                    && (rules.is_empty() || rule_names.iter().any(|r| rules.contains_key(r)))
+            }
+            _ => false,
+        }
+    }
+
+    /// Is the disabled region closed by the enable toggle, a bare enable closes all,
+    /// otherwise only the regions disabled any of the enabled rules.
+    pub fn is_closed_by(&self, toggle: &Toggle) -> bool {
+        let (Toggle::Disable(rules), Toggle::Enable(enabled)) = (&self.toggle, toggle) else {
+            return false;
+        };
+
+        enabled.is_empty() || rules.is_empty() || rules.keys().any(|r| enabled.contains_key(r))
+    }
+
+    /// The rule names in the directive, e.g.: `["space-word"]`
+    pub fn rule_names(&self) -> Vec<&str> {
+        match &self.toggle {
+            Toggle::Disable(rules) | Toggle::Enable(rules) => {
+                let mut names = rules.keys().map(|k| k.as_str()).collect::<Vec<_>>();
+                names.sort();
+                names
+            }
+            Toggle::None => vec![],
+        }
+    }
+}
+
 impl Default for Toggle {
     fn default() -> Self {
         Toggle::enable(vec![])
@@ -123,35 +243,52 @@ impl Toggle {
     }
 }
 
+fn rule_names(pair: pest::iterators::Pair<'_, Rule>) -> Vec<&str> {
+    pair.into_inner()
+        .filter(|pair| pair.as_rule() == Rule::rule_name)
+        .map(|pair| pair.as_str())
+        .collect()
+}
+
+/// Parse the toggle of a region, the line toggles are ignored, see `parse_line`.
 pub fn parse(input: &str) -> Toggle {
     if let Ok(pairs) = ToggleParser::parse(Rule::item, input) {
         for pair in pairs {
             match pair.as_rule() {
-                Rule::disable => {
-                    let mut rules = vec![];
-                    for pair in pair.into_inner() {
-                        if pair.as_rule() == Rule::rule_name {
-                            rules.push(pair.as_str());
-                        }
-                    }
+                Rule::disable | Rule::file_disable => return Toggle::disable(rule_names(pair)),
+                Rule::enable => return Toggle::enable(rule_names(pair)),
+                _ => {}
+            }
+        }
+    }
 
-                    return Toggle::disable(rules);
+    Toggle::None
+}
+
+/// Is the `autocorrect: false` to disable the whole file
+pub fn is_file_disable(input: &str) -> bool {
+    ToggleParser::parse(Rule::item, input)
+        .map(|mut pairs| pairs.any(|pair| pair.as_rule() == Rule::file_disable))
+        .unwrap_or(false)
+}
+
+/// Parse the toggle for a single line, e.g.: `autocorrect-disable-next-line space-word`
+pub fn parse_line(input: &str) -> Option<(LineScope, Toggle)> {
+    if let Ok(pairs) = ToggleParser::parse(Rule::item, input) {
+        for pair in pairs {
+            match pair.as_rule() {
+                Rule::disable_line => {
+                    return Some((LineScope::Line, Toggle::disable(rule_names(pair))))
                 }
-                Rule::enable => {
-                    let mut rules = vec![];
-                    for pair in pair.into_inner() {
-                        if pair.as_rule() == Rule::rule_name {
-                            rules.push(pair.as_str());
-                        }
-                    }
-                    return Toggle::enable(rules);
+                Rule::disable_next_line => {
+                    return Some((LineScope::NextLine, Toggle::disable(rule_names(pair))))
                 }
                 _ => {}
             }
         }
     }
 
-    Toggle::None
+    None
 }
 
 #[cfg(test)]
@@ -226,6 +363,95 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_parse_line() {
+        assert_eq!(
+            Some((LineScope::NextLine, Toggle::disable(vec![]))),
+            parse_line("// autocorrect-disable-next-line")
+        );
+        assert_eq!(
+            Some((LineScope::NextLine, Toggle::disable(vec![]))),
+            parse_line("<!-- autocorrect-disable-next-line -->")
+        );
+        assert_eq!(
+            Some((
+                LineScope::NextLine,
+                Toggle::disable(vec!["space-word", "fullwidth"])
+            )),
+            parse_line("# autocorrect-disable-next-line space-word, fullwidth")
+        );
+        assert_eq!(
+            Some((LineScope::Line, Toggle::disable(vec!["space-word"]))),
+            parse_line("/* autocorrect-disable-line space-word */")
+        );
+        assert_eq!(None, parse_line("// autocorrect-disable"));
+        assert_eq!(None, parse_line("// hello world"));
+
+        // Not a region toggle
+        assert_eq!(Toggle::none(), parse("// autocorrect-disable-next-line"));
+        assert_eq!(Toggle::none(), parse("// autocorrect-disable-line foo"));
+    }
+
+    #[test]
+    fn it_parse_file_disable() {
+        assert!(is_file_disable("// autocorrect: false"));
+        assert!(is_file_disable("# autocorrect:false"));
+        assert!(!is_file_disable("// autocorrect-disable"));
+        assert!(!is_file_disable("// autocorrect: true"));
+        assert_eq!(Toggle::disable(vec![]), parse("// autocorrect: false"));
+        assert_eq!(
+            Toggle::disable(vec!["foo"]),
+            parse("// autocorrect: false foo")
+        );
+    }
+
+    #[test]
+    fn test_directive() {
+        let directive = Directive::new(
+            DirectiveKind::Region,
+            Toggle::disable(vec!["space-word", "fullwidth"]),
+            "// autocorrect-disable space-word, fullwidth\n",
+            (2, 3),
+        );
+        assert_eq!(
+            "// autocorrect-disable space-word, fullwidth",
+            directive.text
+        );
+        assert_eq!(vec!["fullwidth", "space-word"], directive.rule_names());
+        assert!(!directive.closed);
+        assert!(directive.is_applied(10));
+        assert!(directive.is_suppressed(&["space-word".to_owned()]));
+        assert!(!directive.is_suppressed(&["spellcheck".to_owned()]));
+        assert!(!directive.is_suppressed(&[]));
+        assert!(directive.is_closed_by(&Toggle::enable(vec![])));
+        assert!(directive.is_closed_by(&Toggle::enable(vec!["fullwidth"])));
+        assert!(!directive.is_closed_by(&Toggle::enable(vec!["spellcheck"])));
+        assert!(!directive.is_closed_by(&Toggle::disable(vec![])));
+
+        let directive = Directive::new(
+            DirectiveKind::Line(3),
+            Toggle::disable(vec![]),
+            "// autocorrect-disable-next-line",
+            (2, 1),
+        );
+        assert!(directive.closed);
+        assert!(directive.is_applied(3));
+        assert!(!directive.is_applied(4));
+        assert!(directive.is_suppressed(&["spellcheck".to_owned()]));
+        assert!(directive.is_closed_by(&Toggle::enable(vec!["spellcheck"])));
+    }
+
+    #[test]
+    fn test_line_toggles() {
+        let mut toggles = LineToggles::default();
+        assert!(toggles.is_empty());
+
+        toggles.insert(2, Toggle::disable(vec!["foo"]));
+        toggles.insert(2, Toggle::disable(vec!["bar"]));
+        assert_eq!(Some(&Toggle::disable(vec!["foo", "bar"])), toggles.get(2));
+        assert_eq!(None, toggles.get(1));
+    }
+
     #[test]
     fn test_disable_rules() {
         // disable_rules
@@ -5,7 +5,7 @@ use owo_colors::OwoColorize;
 use std::fmt::Write;
 
 /// Diff a LineResult with colorize output.
-pub(crate) fn diff_line_result(line: &LineResult, no_diff_bg_color: bool) -> String {
+pub fn diff_line_result(line: &LineResult, no_diff_bg_color: bool) -> String {
     let mut err_color = Red;
     if line.severity.is_warning() {
         err_color = Yellow;
@@ -68,16 +68,11 @@ pub(crate) fn diff_lines_with_err_color(
                 match diffs.get(i + 1) {
                     Some(diff::Result::Right(y)) => {
                         write!(out, "{}", "-".color(err_color));
-                        let sub_diffs = diff::chars(y, x);
-                        for c in sub_diffs {
-                            match c {
-                                diff::Result::Both(z, _) => {
-                                    write!(out, "{}", z.color(err_color));
-                                }
-                                diff::Result::Right(z) => {
-                                    write!(out, "{}", on_color(z, err_color));
-                                }
-                                _ => (),
+                        for (z, changed) in diff_chars(y, x) {
+                            if changed {
+                                write!(out, "{}", on_color(z, err_color));
+                            } else {
+                                write!(out, "{}", z.color(err_color));
                             }
                         }
                         out.push('\n');
@@ -92,17 +87,11 @@ pub(crate) fn diff_lines_with_err_color(
                 match diffs.get(i - 1) {
                     Some(diff::Result::Left(y)) => {
                         write!(out, "{}", "+".color(Green));
-                        let sub_diffs = diff::chars(y, x);
-
-                        for c in sub_diffs {
-                            match c {
-                                diff::Result::Both(z, _) => {
-                                    write!(out, "{}", z.color(Green));
-                                }
-                                diff::Result::Right(z) => {
-                                    write!(out, "{}", on_color(z, Green));
-                                }
-                                _ => (),
+                        for (z, changed) in diff_chars(y, x) {
+                            if changed {
+                                write!(out, "{}", on_color(z, Green));
+                            } else {
+                                write!(out, "{}", z.color(Green));
                             }
                         }
                         out.push('\n');
@@ -121,6 +110,18 @@ pub(crate) fn diff_lines_with_err_color(
     out
 }
 
+/// Diff the chars of `text` with `base`, returns the chars of `text` and is it changed.
+pub(crate) fn diff_chars(base: &str, text: &str) -> Vec<(char, bool)> {
+    diff::chars(base, text)
+        .into_iter()
+        .filter_map(|c| match c {
+            diff::Result::Both(z, _) => Some((z, false)),
+            diff::Result::Right(z) => Some((z, true)),
+            diff::Result::Left(_) => None,
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
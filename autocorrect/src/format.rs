@@ -81,6 +81,11 @@ mod tests {
         let cases = map![
             "!sm" => "!sm",
             "Hello world!" => "Hello world!",
+            "中文!한국어" => "中文！한국어",
+            "中文 -中文" => "中文 - 中文",
+            "測試100 ：100，；」" => "測試 100:100，；」",
+            "」 Hello/測試, " => "」Hello/測試，",
+            "3.14）  100/@：」測試3.14測試" => "3.14）100/@：」測試 3.14 測試",
             "部署到heroku有问题网页不能显示" => "部署到 heroku 有问题网页不能显示",
             "[北京]美企聘web大型应用开发高手-Ruby" => "[北京] 美企聘 web 大型应用开发高手-Ruby",
             "[成都](团800)招聘Rails工程师" => "[成都](团 800) 招聘 Rails 工程师",
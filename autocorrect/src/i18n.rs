@@ -0,0 +1,745 @@
+// autocorrect: false
+//! Check the translation file with the source file, e.g.: `locales/zh-CN.yml` with `locales/en.yml`.
+use pest::iterators::{Pair, Pairs};
+use pest::RuleType;
+use regex::Regex;
+
+use crate::result::{LineResult, LintResult, Severity};
+use crate::rule::{placeholder, CJK_RE};
+
+lazy_static! {
+    /// Match the locale name, e.g.: `en`, `zh-CN`, `zh_Hant`
+    static ref LOCALE_RE: Regex = regexp!("{}", r"^[a-z]{2}([-_][a-zA-Z]{2,4})?$");
+}
+
+/// A key and value in the translation file, the nested keys are joined by `.`, e.g.: `home.title`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Entry {
+    pub key: String,
+    pub value: String,
+    pub line: usize,
+    pub col: usize,
+}
+
+impl Entry {
+    fn new<R: RuleType>(key: String, pair: &Pair<R>) -> Self {
+        let (line, col) = pair.line_col();
+        Self {
+            key,
+            value: unquote(pair.as_str()).to_owned(),
+            line,
+            col,
+        }
+    }
+}
+
+/// Check the target translation file with the source file.
+///
+/// Returns the `LintResult` of the target file, and the `LintResult` of the source file if there are
+/// missing keys that have no parent or sibling key in the target to report at.
+///
+/// - Missing keys in the target.
+/// - Untranslated values, the target value is the same as the source value.
+/// - Mismatched placeholders, e.g.: `%s`, `{name}`.
+/// - The value is likely in the wrong language, by the ratio of the CJK and Latin letters.
+///
+/// ```
+/// let source = "en:\n  hello: Hello %{name}\n  bye: Bye\n";
+/// let target = "zh-CN:\n  hello: 你好 %{user}\n";
+///
+/// let results = autocorrect::i18n::check(source, "en.yml", target, "zh-CN.yml");
+/// assert_eq!(1, results.len());
+/// assert_eq!(2, results[0].errors_count());
+/// ```
+pub fn check(
+    source: &str,
+    source_filename: &str,
+    target: &str,
+    target_filename: &str,
+) -> Vec<LintResult> {
+    let mut result = LintResult::new(target);
+    result.filepath = String::from(target_filename);
+    let mut source_result = LintResult::new(source);
+    source_result.filepath = String::from(source_filename);
+
+    let entries =
+        crate::code::i18n_entries_for(source, source_filename).and_then(|source_entries| {
+            crate::code::i18n_entries_for(target, target_filename)
+                .map(|target_entries| (source_entries, target_entries))
+        });
+    let (mut source_entries, mut target_entries) = match entries {
+        Ok(entries) => entries,
+        Err(err) => {
+            result.error = err;
+            return vec![result];
+        }
+    };
+
+    strip_locale_root(&mut source_entries);
+    let target_locale = strip_locale_root(&mut target_entries);
+    let target_is_cjk = target_locale
+        .or_else(|| locale_of_filename(target_filename))
+        .map(|locale| is_cjk_locale(&locale))
+        .unwrap_or_else(|| is_mostly_cjk(&target_entries));
+
+    // The keys of YAML and JSON are nested by `.`, others are flat, e.g.: the msgid of Gettext.
+    let nested = matches!(
+        crate::code::match_filename(target_filename).as_str(),
+        "yaml" | "json"
+    );
+    for (index, source_entry) in source_entries.iter().enumerate() {
+        let Some(target_entry) = target_entries.iter().find(|e| e.key == source_entry.key) else {
+            match nearest_entry(&source_entries, index, &target_entries, nested) {
+                Some(nearest) => result.lines.push(LineResult {
+                    line: nearest.line,
+                    col: nearest.col,
+                    severity: Severity::Error,
+                    message: format!(
+                        "Missing key `{}`, it is at line {} in the source.",
+                        source_entry.key, source_entry.line
+                    ),
+                    ..Default::default()
+                }),
+                None => source_result.lines.push(LineResult {
+                    line: source_entry.line,
+                    col: source_entry.col,
+                    severity: Severity::Error,
+                    message: format!(
+                        "Missing key `{}` in `{}`.",
+                        source_entry.key, target_filename
+                    ),
+                    ..Default::default()
+                }),
+            }
+            continue;
+        };
+
+        let mut push = |severity: Severity, message: String| {
+            result.lines.push(LineResult {
+                line: target_entry.line,
+                col: target_entry.col,
+                old: target_entry.value.clone(),
+                new: target_entry.value.clone(),
+                severity,
+                message,
+                ..Default::default()
+            });
+        };
+
+        let source_placeholders = placeholders(&source_entry.value);
+        let target_placeholders = placeholders(&target_entry.value);
+        if source_placeholders != target_placeholders {
+            push(
+                Severity::Error,
+                format!(
+                    "Mismatched placeholders of `{}`, source: {}, target: {}.",
+                    target_entry.key,
+                    join_placeholders(&source_placeholders),
+                    join_placeholders(&target_placeholders)
+                ),
+            );
+        }
+
+        if target_entry.value == source_entry.value {
+            if target_entry.value.chars().any(char::is_alphabetic) {
+                push(
+                    Severity::Warning,
+                    format!("Untranslated value of `{}`.", target_entry.key),
+                );
+            }
+            continue;
+        }
+
+        let ratio = cjk_ratio(&target_entry.value);
+        if ratio.is_some_and(|ratio| {
+            if target_is_cjk {
+                ratio < 0.1
+            } else {
+                ratio > 0.5
+            }
+        }) {
+            push(
+                Severity::Warning,
+                format!(
+                    "The value of `{}` is likely in the wrong language.",
+                    target_entry.key
+                ),
+            );
+        }
+    }
+
+    let mut results = vec![result];
+    if !source_result.lines.is_empty() {
+        results.push(source_result);
+    }
+    results
+}
+
+/// The target entry to report the missing key of `source_entries[index]` at.
+///
+/// It is the entry in the deepest same parent (the sibling, or the child of the ancestors),
+/// and the nearest one in the source order, the previous one is preferred.
+fn nearest_entry<'a>(
+    source_entries: &[Entry],
+    index: usize,
+    target_entries: &'a [Entry],
+    nested: bool,
+) -> Option<&'a Entry> {
+    let parent = parent_key(&source_entries[index].key, nested);
+
+    target_entries
+        .iter()
+        .filter_map(|entry| {
+            let entry_parent = parent_key(&entry.key, nested);
+            let depth = common_depth(parent, entry_parent);
+            if depth == 0 && !(parent.is_empty() && entry_parent.is_empty()) {
+                return None;
+            }
+
+            let position = source_entries
+                .iter()
+                .position(|e| e.key == entry.key)
+                .unwrap_or(usize::MAX);
+            Some((depth, position, entry))
+        })
+        .max_by_key(|(depth, position, _)| {
+            (
+                *depth,
+                std::cmp::Reverse((position.abs_diff(index), *position > index)),
+            )
+        })
+        .map(|(_, _, entry)| entry)
+}
+
+/// The parent of the nested key, e.g.: `home` of `home.title`, empty for the top level or flat key.
+fn parent_key(key: &str, nested: bool) -> &str {
+    if !nested {
+        return "";
+    }
+
+    key.rsplit_once('.').map(|(parent, _)| parent).unwrap_or("")
+}
+
+/// The count of the same leading segments of the two keys.
+fn common_depth(a: &str, b: &str) -> usize {
+    if a.is_empty() || b.is_empty() {
+        return 0;
+    }
+
+    a.split('.')
+        .zip(b.split('.'))
+        .take_while(|(a, b)| a == b)
+        .count()
+}
+
+/// The sorted placeholders in the text.
+fn placeholders(text: &str) -> Vec<&str> {
+    let mut placeholders = placeholder::find(text)
+        .into_iter()
+        .map(|(start, end)| &text[start..end])
+        .collect::<Vec<_>>();
+    placeholders.sort_unstable();
+    placeholders
+}
+
+fn join_placeholders(placeholders: &[&str]) -> String {
+    if placeholders.is_empty() {
+        return String::from("none");
+    }
+
+    placeholders
+        .iter()
+        .map(|p| format!("`{p}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The ratio of the CJK chars in the CJK chars and Latin letters, `None` if not enough letters.
+fn cjk_ratio(text: &str) -> Option<f64> {
+    let mut cjk = 0;
+    let mut latin = 0;
+    let mut last = 0;
+    let ranges = placeholder::find(text);
+    // Skip the placeholders, e.g.: `%{name}`
+    for (start, end) in ranges.iter().copied().chain([(text.len(), text.len())]) {
+        for c in text[last..start].chars() {
+            if c.is_ascii_alphabetic() {
+                latin += 1;
+            } else if CJK_RE.is_match(c.encode_utf8(&mut [0; 4])) {
+                cjk += 1;
+            }
+        }
+        last = end;
+    }
+
+    if cjk + latin < 2 {
+        return None;
+    }
+
+    Some(cjk as f64 / (cjk + latin) as f64)
+}
+
+fn is_mostly_cjk(entries: &[Entry]) -> bool {
+    let ratios = entries
+        .iter()
+        .filter_map(|e| cjk_ratio(&e.value))
+        .collect::<Vec<_>>();
+    !ratios.is_empty() && ratios.iter().sum::<f64>() / ratios.len() as f64 > 0.5
+}
+
+fn is_cjk_locale(locale: &str) -> bool {
+    matches!(locale.get(..2), Some("zh" | "ja" | "ko"))
+}
+
+/// The locale in the filename, e.g.: `locales/zh-CN.yml`, `zh-CN/messages.json`
+fn locale_of_filename(filename: &str) -> Option<String> {
+    let path = std::path::Path::new(filename);
+    let stem = path.file_stem().and_then(|s| s.to_str());
+    let dir = path
+        .parent()
+        .and_then(|p| p.file_name())
+        .and_then(|s| s.to_str());
+
+    [stem, dir]
+        .into_iter()
+        .flatten()
+        .find(|name| LOCALE_RE.is_match(name))
+        .map(String::from)
+}
+
+/// Remove the root key if it is the locale, e.g.: `en:` in Rails, returns the locale.
+fn strip_locale_root(entries: &mut [Entry]) -> Option<String> {
+    let root = entries.first()?.key.split('.').next()?.to_owned();
+    if !LOCALE_RE.is_match(&root)
+        || !entries
+            .iter()
+            .all(|e| e.key.starts_with(&format!("{root}.")))
+    {
+        return None;
+    }
+
+    for entry in entries.iter_mut() {
+        entry.key = entry.key[root.len() + 1..].to_owned();
+    }
+
+    Some(root)
+}
+
+fn unquote(text: &str) -> &str {
+    let text = text.trim();
+    for quote in ['"', '\''] {
+        if text.len() >= 2 && text.starts_with(quote) && text.ends_with(quote) {
+            return &text[1..text.len() - 1];
+        }
+    }
+
+    text
+}
+
+fn rule_name<R: RuleType>(pair: &Pair<R>) -> String {
+    format!("{:?}", pair.as_rule())
+}
+
+/// The YAML keys are nested by the indent.
+pub(crate) fn yaml_entries<R: RuleType>(pairs: Pairs<R>) -> Vec<Entry> {
+    let mut entries = vec![];
+    // The keys of the parents with the indent
+    let mut keys: Vec<(usize, String)> = vec![];
+
+    for pair in pairs {
+        match rule_name(&pair).as_str() {
+            "key_part" => {
+                let text = pair.as_str().trim_end();
+                let indent = text.len() - text.trim_start().len();
+                let key = unquote(text.trim_start().trim_end_matches(':'));
+
+                while keys.last().is_some_and(|(i, _)| *i >= indent) {
+                    keys.pop();
+                }
+                keys.push((indent, key.to_owned()));
+            }
+            "string" => {
+                // Skip the parent key, and the block scalars, e.g.: `|`
+                let value = pair.as_str().trim();
+                if value.is_empty() || value == "|" || value == ">" {
+                    continue;
+                }
+
+                let key = keys
+                    .iter()
+                    .map(|(_, k)| k.as_str())
+                    .collect::<Vec<_>>()
+                    .join(".");
+                entries.push(Entry::new(key, &pair));
+            }
+            _ => {}
+        }
+    }
+
+    entries
+}
+
+/// The JSON keys are nested by the objects, and the index of the arrays.
+pub(crate) fn json_entries<R: RuleType>(pairs: Pairs<R>) -> Vec<Entry> {
+    let mut entries = vec![];
+    json_value_entries(pairs, &[], false, &mut entries);
+    entries
+}
+
+fn json_value_entries<R: RuleType>(
+    pairs: Pairs<R>,
+    keys: &[String],
+    in_array: bool,
+    entries: &mut Vec<Entry>,
+) {
+    let mut index = 0;
+    for pair in pairs {
+        let (key, value) = match rule_name(&pair).as_str() {
+            "pair" => {
+                let mut inner = pair.into_inner();
+                let key = inner
+                    .next()
+                    .map(|k| unquote(k.as_str().trim_end_matches(':')).to_owned())
+                    .unwrap_or_default();
+                let Some(value) = inner.find(|p| rule_name(p) != "WHITESPACE") else {
+                    continue;
+                };
+                (key, value)
+            }
+            // The items of an array
+            "string" | "number" | "bool" | "null" | "object" | "array" if in_array => {
+                index += 1;
+                ((index - 1).to_string(), pair)
+            }
+            // The root object or array
+            "object" | "array" => {
+                let in_array = rule_name(&pair) == "array";
+                json_value_entries(pair.into_inner(), keys, in_array, entries);
+                continue;
+            }
+            _ => continue,
+        };
+
+        let keys = [keys, &[key]].concat();
+        match rule_name(&value).as_str() {
+            "string" => entries.push(Entry::new(keys.join("."), &value)),
+            "object" => json_value_entries(value.into_inner(), &keys, false, entries),
+            "array" => json_value_entries(value.into_inner(), &keys, true, entries),
+            _ => {}
+        }
+    }
+}
+
+/// The `.strings` file, e.g.: `"hello" = "Hello";`
+pub(crate) fn strings_entries<R: RuleType>(pairs: Pairs<R>) -> Vec<Entry> {
+    let mut entries = vec![];
+    let mut key = None;
+
+    for pair in pairs {
+        match rule_name(&pair).as_str() {
+            "key" => key = Some(unquote(pair.as_str()).to_owned()),
+            "string" => {
+                if let Some(key) = key.take() {
+                    entries.push(Entry::new(key, &pair));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    entries
+}
+
+/// The gettext file, the key is the `msgid` (with the `msgctxt`), and the value is the `msgstr`.
+///
+/// The `msgid` is the value if the `msgstr` is empty, e.g.: the template file.
+pub(crate) fn gettext_entries<R: RuleType>(pairs: Pairs<R>) -> Vec<Entry> {
+    let mut entries: Vec<Entry> = vec![];
+    let mut ids = vec![];
+    let mut context = String::new();
+    let mut id = String::new();
+    let mut plurals = 0;
+    // The next strings are the continuation of the `msgid` or the `msgstr`
+    let mut last = "";
+
+    for pair in pairs {
+        match rule_name(&pair).as_str() {
+            "key" => {
+                let func = pair
+                    .as_str()
+                    .split(['[', ' ', '"'])
+                    .next()
+                    .unwrap_or_default();
+                let value = pair
+                    .into_inner()
+                    .last()
+                    .map(|s| unquote(s.as_str()).to_owned())
+                    .unwrap_or_default();
+                match func {
+                    "msgctxt" => context = value,
+                    "msgid" => {
+                        // The `msgctxt` is only for the next `msgid`
+                        if last != "msgctxt" {
+                            context.clear();
+                        }
+                        id = value;
+                        plurals = 0;
+                    }
+                    _ => {}
+                }
+                last = func;
+            }
+            "value" => {
+                let Some(string) = pair.into_inner().last() else {
+                    continue;
+                };
+
+                let mut key = if context.is_empty() {
+                    id.clone()
+                } else {
+                    format!("{context}|{id}")
+                };
+                // The plural forms, e.g.: `msgstr[1]`
+                if plurals > 0 {
+                    key = format!("{key}[{plurals}]");
+                }
+                plurals += 1;
+                last = "msgstr";
+
+                entries.push(Entry::new(key, &string));
+                ids.push(id.clone());
+            }
+            "string" => {
+                let value = unquote(pair.as_str());
+                match last {
+                    "msgctxt" => context.push_str(value),
+                    "msgid" => id.push_str(value),
+                    "msgstr" => {
+                        if let Some(entry) = entries.last_mut() {
+                            entry.value.push_str(value);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for (entry, id) in entries.iter_mut().zip(ids) {
+        if entry.value.is_empty() {
+            entry.value = id;
+        }
+    }
+
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    fn entries(text: &str, filename: &str) -> Vec<(String, String)> {
+        crate::code::i18n_entries_for(text, filename)
+            .unwrap()
+            .into_iter()
+            .map(|e| (e.key, e.value))
+            .collect()
+    }
+
+    fn pairs(items: &[(&str, &str)]) -> Vec<(String, String)> {
+        items
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_entries() {
+        let yaml = indoc! {r#"
+        en:
+          # Comment
+          home:
+            title: "Home"
+            desc: 'Hello %{name}'
+          about: About
+        "#};
+        assert_eq!(
+            pairs(&[
+                ("en.home.title", "Home"),
+                ("en.home.desc", "Hello %{name}"),
+                ("en.about", "About")
+            ]),
+            entries(yaml, "en.yml")
+        );
+
+        let json = r#"{"home": {"title": "Home", "items": ["One", "Two"]}, "count": 1}"#;
+        assert_eq!(
+            pairs(&[
+                ("home.title", "Home"),
+                ("home.items.0", "One"),
+                ("home.items.1", "Two")
+            ]),
+            entries(json, "en.json")
+        );
+
+        let strings = indoc! {r#"
+        /* Comment */
+        "home.title" = "Home";
+        "hello" = "Hello %@";
+        "#};
+        assert_eq!(
+            pairs(&[("home.title", "Home"), ("hello", "Hello %@")]),
+            entries(strings, "Localizable.strings")
+        );
+
+        let gettext = indoc! {r#"
+        #: src/main.rs:1
+        msgid "Home"
+        msgstr "首页"
+
+        msgctxt "menu"
+        msgid "Open"
+        msgstr ""
+
+        msgid "Long"
+        msgstr ""
+        "Long "
+        "text"
+        "#};
+        assert_eq!(
+            pairs(&[
+                ("Home", "首页"),
+                ("menu|Open", "Open"),
+                ("Long", "Long text")
+            ]),
+            entries(gettext, "zh-CN.po")
+        );
+
+        assert!(crate::code::i18n_entries_for("", "foo.rb").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let source = indoc! {r#"
+        en:
+          title: Home
+          hello: "Hello %{name}, you have %d messages"
+          about: About us
+          bye: Bye
+          brand: AutoCorrect
+        "#};
+        let target = indoc! {r#"
+        zh-CN:
+          title: 首页
+          hello: "你好 %{user}，你有 %d 条消息"
+          about: About us
+          brand: AutoCorrect
+        "#};
+
+        let results = check(source, "locales/en.yml", target, "locales/zh-CN.yml");
+        assert_eq!(1, results.len());
+        let lines = results[0]
+            .lines
+            .iter()
+            .map(|l| (l.line, l.severity, l.message.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (
+                    3,
+                    Severity::Error,
+                    "Mismatched placeholders of `hello`, source: `%d`, `%{name}`, target: `%d`, `%{user}`."
+                ),
+                (4, Severity::Warning, "Untranslated value of `about`."),
+                (4, Severity::Error, "Missing key `bye`, it is at line 5 in the source."),
+                (5, Severity::Warning, "Untranslated value of `brand`."),
+            ],
+            lines
+        );
+
+        let target = "title: 首页\nabout: About our team\n";
+        let result = &check("title: Home\nabout: About\n", "en.yml", target, "zh-CN.yml")[0];
+        assert_eq!(1, result.lines.len());
+        assert_eq!(
+            "The value of `about` is likely in the wrong language.",
+            result.lines[0].message
+        );
+        assert_eq!((2, 8), (result.lines[0].line, result.lines[0].col));
+
+        let results = check("title: Home\n", "en.yml", "{}", "zh-CN.rb");
+        assert_eq!(1, results.len());
+        assert!(results[0].has_error());
+    }
+
+    #[test]
+    fn test_check_missing_keys() {
+        let source = indoc! {r#"
+        home:
+          title: Home
+          nav:
+            about: About
+            help: Help
+          footer: Footer
+        user:
+          name: Name
+        bye: Bye
+        "#};
+        let target = indoc! {r#"
+        home:
+          title: 首页
+          footer: 页脚
+        "#};
+
+        let results = check(source, "en.yml", target, "zh-CN.yml");
+        let lines = results
+            .iter()
+            .flat_map(|result| {
+                result
+                    .lines
+                    .iter()
+                    .map(|l| (result.filepath.as_str(), l.line, l.col, l.message.as_str()))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (
+                    "zh-CN.yml",
+                    2,
+                    10,
+                    "Missing key `home.nav.about`, it is at line 4 in the source."
+                ),
+                (
+                    "zh-CN.yml",
+                    3,
+                    11,
+                    "Missing key `home.nav.help`, it is at line 5 in the source."
+                ),
+                ("en.yml", 8, 9, "Missing key `user.name` in `zh-CN.yml`."),
+                ("en.yml", 9, 6, "Missing key `bye` in `zh-CN.yml`."),
+            ],
+            lines
+        );
+
+        // The keys of Gettext are flat, all the keys are siblings.
+        let source = "msgid \"Done.\"\nmsgstr \"Done.\"\n\nmsgid \"Cancel\"\nmsgstr \"Cancel\"\n";
+        let target = "msgid \"Cancel\"\nmsgstr \"取消\"\n";
+        let results = check(source, "en.po", target, "zh-CN.po");
+        assert_eq!(1, results.len());
+        assert_eq!(2, results[0].lines[0].line);
+        assert_eq!(
+            "Missing key `Done.`, it is at line 2 in the source.",
+            results[0].lines[0].message
+        );
+    }
+
+    #[test]
+    fn test_cjk_ratio() {
+        assert_eq!(Some(1.0), cjk_ratio("你好 %{name}"));
+        assert_eq!(Some(0.0), cjk_ratio("Hello %{name}"));
+        assert_eq!(Some(0.5), cjk_ratio("你好 Hi"));
+        assert_eq!(None, cjk_ratio("%s 1"));
+    }
+}
@@ -145,13 +145,24 @@ mod rule;
 mod serde_any;
 
 pub mod config;
+pub mod i18n;
 pub mod ignorer;
 
-pub use code::{format_for, get_file_extension, is_support_type, lint_for};
+pub use code::{
+    format_for, format_for_checked, get_file_extension, is_support_type, lint_for, match_filename,
+    CheckedResult,
+};
 pub use config::Config;
+pub use diff::diff_line_result;
 pub use format::*;
-pub use result::{json, rdjson, FormatResult, LineResult, LintResult, Severity};
-pub use rule::{halfwidth, spellcheck};
+pub use result::{
+    checkstyle, github, gitlab, html, json, junit, patch, rdjson, to_lint_results_report,
+    FormatResult, LineResult, LintResult, ParseError, Severity,
+};
+pub use rule::{
+    cjk_count, halfwidth, rule_info, rules, spellcheck, Context, Engine, Phase, RuleExample,
+    RuleInfo, RuleTrait as Rule,
+};
 
 #[cfg(test)]
 mod tests {
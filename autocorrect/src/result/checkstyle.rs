@@ -0,0 +1,102 @@
+//! Checkstyle XML format, for Jenkins, SonarQube and the other tools support it.
+//! https://checkstyle.org/
+use super::{xml, LineResult, LintResult, Severity};
+
+fn to_severity_str(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Pass => "info",
+    }
+}
+
+fn to_source(line_result: &LineResult) -> String {
+    if line_result.rules.is_empty() {
+        "autocorrect".to_owned()
+    } else {
+        format!("autocorrect.{}", line_result.rules.join(","))
+    }
+}
+
+fn to_message(line_result: &LineResult) -> String {
+    let message = format!("{} → {}", line_result.old, line_result.new);
+    if line_result.message.is_empty() {
+        message
+    } else {
+        format!("{}: {}", line_result.message, message)
+    }
+}
+
+#[doc(hidden)]
+pub fn to_lint_results_checkstyle(lint_results: Vec<LintResult>) -> String {
+    let mut out = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    out.push_str("\n<checkstyle version=\"4.3\">\n");
+
+    for lint_result in lint_results.iter() {
+        out.push_str(&format!(
+            "<file name=\"{}\">\n",
+            xml::escape(&lint_result.filepath.replace("./", ""))
+        ));
+
+        if lint_result.has_error() {
+            let (line, col) = lint_result.error_position();
+            out.push_str(&format!(
+                "<error line=\"{}\" column=\"{}\" severity=\"error\" message=\"{}\" source=\"autocorrect\"/>\n",
+                line,
+                col,
+                xml::escape(&lint_result.error)
+            ));
+        }
+
+        for line_result in lint_result
+            .lines
+            .iter()
+            .filter(|line_result| !line_result.severity.is_pass())
+        {
+            out.push_str(&format!(
+                "<error line=\"{}\" column=\"{}\" severity=\"{}\" message=\"{}\" source=\"{}\"/>\n",
+                line_result.line,
+                line_result.col,
+                to_severity_str(line_result.severity),
+                xml::escape(&to_message(line_result)),
+                xml::escape(&to_source(line_result)),
+            ));
+        }
+
+        out.push_str("</file>\n");
+    }
+
+    out.push_str("</checkstyle>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::result::json::crate_test_lint_results;
+
+    #[test]
+    fn test_to_lint_results_checkstyle() {
+        let mut lint_results = crate_test_lint_results();
+        lint_results[0].lines[0].rules = vec!["space-word".to_owned(), "fullwidth".to_owned()];
+        lint_results[0].lines[1].severity = Severity::Warning;
+        lint_results[0].lines[1].old = "这是<b>第2行</b>".to_owned();
+        lint_results[0].lines.push(LineResult {
+            line: 3,
+            col: 1,
+            severity: Severity::Pass,
+            ..Default::default()
+        });
+
+        let expected = indoc::indoc! {r#"
+        <?xml version="1.0" encoding="UTF-8"?>
+        <checkstyle version="4.3">
+        <file name="test/foo/bar.rs">
+        <error line="1" column="1" severity="error" message="hello你好. → hello 你好。" source="autocorrect.space-word,fullwidth"/>
+        <error line="2" column="1" severity="warning" message="这是&lt;b&gt;第2行&lt;/b&gt; → 这是第 2 行" source="autocorrect"/>
+        </file>
+        </checkstyle>
+        "#};
+        assert_eq!(expected, to_lint_results_checkstyle(lint_results));
+    }
+}
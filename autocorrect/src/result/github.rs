@@ -0,0 +1,215 @@
+//! GitHub Actions workflow commands, the lint results are shown as the annotations.
+//! https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions
+use super::{LineResult, LintResult, Severity};
+
+fn to_command(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Warning => "warning",
+        _ => "error",
+    }
+}
+
+/// Escape the message of the workflow command.
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape the property value of the workflow command, e.g.: `file`, `title`.
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+fn title(line_result: &LineResult) -> String {
+    if line_result.rules.is_empty() {
+        "autocorrect".to_owned()
+    } else {
+        format!("autocorrect({})", line_result.rules.join(","))
+    }
+}
+
+fn to_github_annotation(filepath: &str, line_result: &LineResult) -> String {
+    let old_lines = line_result.old.split('\n').collect::<Vec<_>>();
+    let end_line = line_result.line + old_lines.len() - 1;
+    let last_line_len = old_lines.last().unwrap_or(&"").chars().count();
+    let end_col = if old_lines.len() > 1 {
+        last_line_len + 1
+    } else {
+        line_result.col + last_line_len
+    };
+
+    let mut message = format!("{} → {}", line_result.old, line_result.new);
+    if !line_result.message.is_empty() {
+        message = format!("{}\n{}", line_result.message, message);
+    }
+
+    format!(
+        "::{} file={},line={},col={},endLine={},endColumn={},title={}::{}",
+        to_command(line_result.severity),
+        escape_property(filepath),
+        line_result.line,
+        line_result.col,
+        end_line,
+        end_col,
+        escape_property(&title(line_result)),
+        escape_data(&message),
+    )
+}
+
+#[doc(hidden)]
+pub fn to_lint_results_github(lint_results: Vec<LintResult>) -> String {
+    let mut annotations = vec![];
+    for lint_result in lint_results.iter() {
+        let filepath = lint_result.filepath.replace("./", "");
+        if lint_result.has_error() {
+            let (line, col) = lint_result.error_position();
+            annotations.push(format!(
+                "::error file={},line={},col={},title=autocorrect::{}",
+                escape_property(&filepath),
+                line,
+                col,
+                escape_data(&lint_result.error)
+            ));
+        }
+
+        annotations.extend(
+            lint_result
+                .lines
+                .iter()
+                .filter(|line_result| !line_result.severity.is_pass())
+                .map(|line_result| to_github_annotation(&filepath, line_result)),
+        );
+    }
+
+    annotations.join("\n")
+}
+
+/// Escape the text in the cell of Markdown table.
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+/// The Markdown for the job summary, write to the file of `GITHUB_STEP_SUMMARY`.
+#[doc(hidden)]
+pub fn to_lint_results_github_summary(lint_results: &[LintResult]) -> String {
+    let mut rows = vec![];
+    let mut errors_count = 0;
+    let mut warnings_count = 0;
+
+    for lint_result in lint_results.iter() {
+        let filepath = escape_cell(&lint_result.filepath.replace("./", ""));
+        if lint_result.has_error() {
+            errors_count += 1;
+            rows.push(format!(
+                "| `{}` | | Error | | {} | |",
+                filepath,
+                escape_cell(&lint_result.error)
+            ));
+        }
+
+        for line_result in lint_result.lines.iter() {
+            let severity = match line_result.severity {
+                Severity::Error => "Error",
+                Severity::Warning => "Warning",
+                Severity::Pass => continue,
+            };
+            if line_result.severity.is_error() {
+                errors_count += 1;
+            } else {
+                warnings_count += 1;
+            }
+
+            rows.push(format!(
+                "| `{}` | {}:{} | {} | {} | {} | {} |",
+                filepath,
+                line_result.line,
+                line_result.col,
+                severity,
+                line_result.rules.join(", "),
+                escape_cell(&line_result.old),
+                escape_cell(&line_result.new),
+            ));
+        }
+    }
+
+    let mut summary = String::from("### AutoCorrect\n\n");
+    if rows.is_empty() {
+        summary.push_str("No issues found.\n");
+        return summary;
+    }
+
+    summary.push_str(&format!(
+        "Error: {errors_count}, Warning: {warnings_count}\n\n"
+    ));
+    summary.push_str("| File | Line | Severity | Rule | Old | New |\n");
+    summary.push_str("| --- | --- | --- | --- | --- | --- |\n");
+    for row in rows {
+        summary.push_str(&row);
+        summary.push('\n');
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::result::json::crate_test_lint_results;
+    use crate::result::Results;
+
+    #[test]
+    fn test_to_lint_results_github() {
+        let mut lint_results = crate_test_lint_results();
+        lint_results[0].lines[0].rules = vec!["space-word".to_owned(), "fullwidth".to_owned()];
+        lint_results[0].lines[1].severity = Severity::Warning;
+        lint_results[0].lines[1].message = "100% sure".to_owned();
+
+        let expected = [
+            "::error file=test/foo/bar.rs,line=1,col=1,endLine=1,endColumn=9,title=autocorrect(space-word%2Cfullwidth)::hello你好. → hello 你好。",
+            "::warning file=test/foo/bar.rs,line=2,col=1,endLine=2,endColumn=6,title=autocorrect::100%25 sure%0A这是第2行 → 这是第 2 行",
+        ]
+        .join("\n");
+        assert_eq!(expected, to_lint_results_github(lint_results));
+
+        let mut lint_result = LintResult::new("");
+        lint_result.filepath = "./foo.vue".to_owned();
+        lint_result.parse_error(crate::result::ParseError {
+            line: 3,
+            col: 5,
+            grammar: "html".to_owned(),
+            expected: vec!["tag".to_owned()],
+            message: "expected tag".to_owned(),
+        });
+        assert_eq!(
+            "::error file=foo.vue,line=3,col=5,title=autocorrect::html parse error: expected tag",
+            to_lint_results_github(vec![lint_result])
+        );
+    }
+
+    #[test]
+    fn test_to_lint_results_github_summary() {
+        let mut lint_results = crate_test_lint_results();
+        lint_results[0].lines[0].rules = vec!["space-word".to_owned()];
+        lint_results[0].lines[1].old = "a|b\n这是第2行".to_owned();
+
+        let expected = indoc::indoc! {r#"
+        ### AutoCorrect
+
+        Error: 2, Warning: 0
+
+        | File | Line | Severity | Rule | Old | New |
+        | --- | --- | --- | --- | --- | --- |
+        | `test/foo/bar.rs` | 1:1 | Error | space-word | hello你好. | hello 你好。 |
+        | `test/foo/bar.rs` | 2:1 | Error |  | a\|b<br>这是第2行 | 这是第 2 行 |
+        "#};
+        assert_eq!(expected, to_lint_results_github_summary(&lint_results));
+
+        assert_eq!(
+            "### AutoCorrect\n\nNo issues found.\n",
+            to_lint_results_github_summary(&[])
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! GitLab Code Quality report
+//! https://docs.gitlab.com/ee/ci/testing/code_quality.html#implement-a-custom-tool
+use std::collections::HashMap;
+
+use super::{LineResult, LintResult, Severity};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct CodeQualityIssue {
+    description: String,
+    check_name: String,
+    fingerprint: String,
+    severity: &'static str,
+    location: CodeQualityLocation,
+}
+
+#[derive(Serialize)]
+struct CodeQualityLocation {
+    path: String,
+    lines: CodeQualityLines,
+}
+
+#[derive(Serialize)]
+struct CodeQualityLines {
+    begin: usize,
+}
+
+fn to_severity_str(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "major",
+        Severity::Warning => "minor",
+        Severity::Pass => "info",
+    }
+}
+
+fn to_check_name(line_result: &LineResult) -> String {
+    if line_result.rules.is_empty() {
+        "autocorrect".to_owned()
+    } else {
+        format!("autocorrect/{}", line_result.rules.join(","))
+    }
+}
+
+/// The FNV-1a 64 hash in hex, it is stable across the platforms and the Rust versions.
+fn fnv1a_hex(parts: &[&str]) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for part in parts {
+        for byte in part.bytes().chain(std::iter::once(0)) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    format!("{hash:016x}")
+}
+
+/// Generate the fingerprint by the path, the check name and the old text, without the line number,
+/// so the issue is the same one after the lines are moved.
+///
+/// The same issues in a file are distinguished by the occurrence.
+struct Fingerprints {
+    occurrences: HashMap<String, usize>,
+}
+
+impl Fingerprints {
+    fn new() -> Self {
+        Self {
+            occurrences: HashMap::new(),
+        }
+    }
+
+    fn next(&mut self, path: &str, check_name: &str, old: &str) -> String {
+        let key = fnv1a_hex(&[path, check_name, old]);
+        let occurrence = self.occurrences.entry(key.clone()).or_default();
+        *occurrence += 1;
+
+        if *occurrence == 1 {
+            key
+        } else {
+            fnv1a_hex(&[&key, &occurrence.to_string()])
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn to_lint_results_gitlab(lint_results: Vec<LintResult>) -> String {
+    let mut fingerprints = Fingerprints::new();
+    let mut issues = vec![];
+
+    for lint_result in lint_results.iter() {
+        let path = lint_result.filepath.replace("./", "");
+        if lint_result.has_error() {
+            let (line, _) = lint_result.error_position();
+            issues.push(CodeQualityIssue {
+                description: lint_result.error.clone(),
+                check_name: "autocorrect".to_owned(),
+                fingerprint: fingerprints.next(&path, "autocorrect", &lint_result.error),
+                severity: "critical",
+                location: CodeQualityLocation {
+                    path: path.clone(),
+                    lines: CodeQualityLines { begin: line },
+                },
+            });
+        }
+
+        for line_result in lint_result.lines.iter() {
+            let check_name = to_check_name(line_result);
+            let mut description = format!("{} → {}", line_result.old, line_result.new);
+            if !line_result.message.is_empty() {
+                description = format!("{}: {}", line_result.message, description);
+            }
+
+            issues.push(CodeQualityIssue {
+                description,
+                fingerprint: fingerprints.next(&path, &check_name, &line_result.old),
+                check_name,
+                severity: to_severity_str(line_result.severity),
+                location: CodeQualityLocation {
+                    path: path.clone(),
+                    lines: CodeQualityLines {
+                        begin: line_result.line,
+                    },
+                },
+            });
+        }
+    }
+
+    serde_json::to_string(&issues).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::result::json::crate_test_lint_results;
+
+    #[test]
+    fn test_to_lint_results_gitlab() {
+        let mut lint_results = crate_test_lint_results();
+        lint_results[0].lines[0].rules = vec!["space-word".to_owned(), "fullwidth".to_owned()];
+        lint_results[0].lines[1].severity = Severity::Warning;
+
+        let expected = r#"[{"description":"hello你好. → hello 你好。","check_name":"autocorrect/space-word,fullwidth","fingerprint":"8e80a26d1ec795f1","severity":"major","location":{"path":"test/foo/bar.rs","lines":{"begin":1}}},{"description":"这是第2行 → 这是第 2 行","check_name":"autocorrect","fingerprint":"7382916b11cea257","severity":"minor","location":{"path":"test/foo/bar.rs","lines":{"begin":2}}}]"#;
+        let gitlab = to_lint_results_gitlab(lint_results);
+        if expected != gitlab {
+            println!("--------------- gitlab:\n{}", gitlab);
+        }
+        assert_json_eq!(expected, gitlab);
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        let mut fingerprints = Fingerprints::new();
+        let a = fingerprints.next("a.md", "autocorrect/space-word", "你好hello");
+        let b = fingerprints.next("a.md", "autocorrect/space-word", "你好hello");
+        let c = fingerprints.next("b.md", "autocorrect/space-word", "你好hello");
+        assert_ne!(a, b);
+        assert_ne!(a, c);
+
+        // Stable, not depends on the line number or the order of the other files
+        let mut fingerprints = Fingerprints::new();
+        assert_eq!(
+            c,
+            fingerprints.next("b.md", "autocorrect/space-word", "你好hello")
+        );
+        assert_eq!(
+            a,
+            fingerprints.next("a.md", "autocorrect/space-word", "你好hello")
+        );
+        assert_eq!(
+            b,
+            fingerprints.next("a.md", "autocorrect/space-word", "你好hello")
+        );
+
+        assert_eq!("089be207b544f1e4", fnv1a_hex(&["a"]));
+    }
+}
@@ -0,0 +1,258 @@
+//! Self-contained HTML report, for browse the lint results without the terminal.
+//!
+//! A single static file, the styles are inline and no script, the sections are expanded by `<details>`.
+use std::collections::BTreeMap;
+
+use super::{xml, LineResult, LintResult, Severity};
+
+const STYLE: &str = r#"
+body { font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", "PingFang SC", "Microsoft YaHei", sans-serif; margin: 2rem; color: #1f2328; }
+h1 { font-size: 1.5rem; }
+h2 { font-size: 1.2rem; margin-top: 2rem; }
+table { border-collapse: collapse; width: 100%; margin: 0.5rem 0; }
+th, td { border: 1px solid #d0d7de; padding: 4px 8px; text-align: left; vertical-align: top; }
+th { background: #f6f8fa; }
+td.num { text-align: right; width: 6rem; }
+td.pos { white-space: nowrap; width: 6rem; }
+td.old, td.new { white-space: pre-wrap; font-family: ui-monospace, SFMono-Regular, Menlo, monospace; width: 35%; }
+td.old { background: #ffebe9; }
+td.new { background: #e6ffec; }
+del { background: #ff818266; text-decoration: none; }
+ins { background: #4ac26b66; text-decoration: none; }
+details { border: 1px solid #d0d7de; border-radius: 6px; margin: 0.5rem 0; padding: 0.5rem 1rem; }
+summary { cursor: pointer; font-family: ui-monospace, SFMono-Regular, Menlo, monospace; }
+.error { color: #cf222e; }
+.warning { color: #9a6700; }
+"#;
+
+#[derive(Default)]
+struct Counts {
+    files: usize,
+    errors: usize,
+    warnings: usize,
+}
+
+impl Counts {
+    fn add(&mut self, severity: Severity) {
+        match severity {
+            Severity::Error => self.errors += 1,
+            Severity::Warning => self.warnings += 1,
+            Severity::Pass => {}
+        }
+    }
+}
+
+fn to_severity_str(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Pass => "pass",
+    }
+}
+
+fn to_rule_names(line_result: &LineResult) -> Vec<String> {
+    if line_result.rules.is_empty() {
+        vec!["other".to_owned()]
+    } else {
+        line_result.rules.clone()
+    }
+}
+
+fn dirname(filepath: &str) -> &str {
+    match filepath.rfind('/') {
+        Some(i) => &filepath[..i],
+        None => ".",
+    }
+}
+
+/// Highlight the changed chars of `text` by `tag`, the char diff is same as the diff output.
+fn highlight(base: &str, text: &str, tag: &str) -> String {
+    let mut out = String::new();
+    let mut changed_chars = String::new();
+    for (c, changed) in crate::diff::diff_chars(base, text) {
+        if changed {
+            changed_chars.push(c);
+            continue;
+        }
+
+        if !changed_chars.is_empty() {
+            out.push_str(&format!("<{tag}>{}</{tag}>", xml::escape(&changed_chars)));
+            changed_chars.clear();
+        }
+        out.push_str(&xml::escape(&c.to_string()));
+    }
+    if !changed_chars.is_empty() {
+        out.push_str(&format!("<{tag}>{}</{tag}>", xml::escape(&changed_chars)));
+    }
+
+    out
+}
+
+fn counts_table(title: &str, counts: &BTreeMap<String, Counts>, with_files: bool) -> String {
+    let mut out = format!("<h2>{title}</h2>\n<table>\n<tr><th>{title}</th>");
+    if with_files {
+        out.push_str("<th>Files</th>");
+    }
+    out.push_str("<th>Errors</th><th>Warnings</th></tr>\n");
+
+    for (name, count) in counts.iter() {
+        out.push_str(&format!("<tr><td><code>{}</code></td>", xml::escape(name)));
+        if with_files {
+            out.push_str(&format!("<td class=\"num\">{}</td>", count.files));
+        }
+        out.push_str(&format!(
+            "<td class=\"num\">{}</td><td class=\"num\">{}</td></tr>\n",
+            count.errors, count.warnings
+        ));
+    }
+    out.push_str("</table>\n");
+
+    out
+}
+
+fn file_section(filepath: &str, lint_result: &LintResult, count: &Counts) -> String {
+    let mut out = format!(
+        "<details>\n<summary>{} <span class=\"error\">{} errors</span>, <span class=\"warning\">{} warnings</span></summary>\n",
+        xml::escape(filepath),
+        count.errors,
+        count.warnings
+    );
+
+    if lint_result.has_error() {
+        let (line, col) = lint_result.error_position();
+        out.push_str(&format!(
+            "<p class=\"error\">{}:{} {}</p>\n",
+            line,
+            col,
+            xml::escape(&lint_result.error)
+        ));
+    }
+
+    if !lint_result.lines.is_empty() {
+        out.push_str("<table>\n<tr><th>Line</th><th>Severity</th><th>Rule</th><th>Before</th><th>After</th></tr>\n");
+        for line_result in lint_result.lines.iter() {
+            let severity = to_severity_str(line_result.severity);
+            let mut rules = xml::escape(&line_result.rules.join(", "));
+            if !line_result.message.is_empty() {
+                rules.push_str(&format!("<br>{}", xml::escape(&line_result.message)));
+            }
+
+            out.push_str(&format!(
+                "<tr><td class=\"pos\">{}:{}</td><td class=\"{severity}\">{severity}</td><td>{}</td><td class=\"old\">{}</td><td class=\"new\">{}</td></tr>\n",
+                line_result.line,
+                line_result.col,
+                rules,
+                highlight(&line_result.new, &line_result.old, "del"),
+                highlight(&line_result.old, &line_result.new, "ins"),
+            ));
+        }
+        out.push_str("</table>\n");
+    }
+    out.push_str("</details>\n");
+
+    out
+}
+
+#[doc(hidden)]
+pub fn to_lint_results_html(lint_results: Vec<LintResult>) -> String {
+    let mut lint_results = lint_results;
+    lint_results.sort_by(|a, b| a.filepath.cmp(&b.filepath));
+
+    let mut total = Counts::default();
+    let mut by_rule: BTreeMap<String, Counts> = BTreeMap::new();
+    let mut by_dir: BTreeMap<String, Counts> = BTreeMap::new();
+    let mut files = String::new();
+
+    for lint_result in lint_results.iter() {
+        let filepath = lint_result.filepath.trim_start_matches("./");
+        let mut count = Counts::default();
+        if lint_result.has_error() {
+            count.errors += 1;
+        }
+        for line_result in lint_result.lines.iter() {
+            count.add(line_result.severity);
+            for rule in to_rule_names(line_result) {
+                by_rule.entry(rule).or_default().add(line_result.severity);
+            }
+        }
+
+        let dir = by_dir.entry(dirname(filepath).to_owned()).or_default();
+        dir.files += 1;
+        dir.errors += count.errors;
+        dir.warnings += count.warnings;
+
+        total.files += 1;
+        total.errors += count.errors;
+        total.warnings += count.warnings;
+
+        files.push_str(&file_section(filepath, lint_result, &count));
+    }
+
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>AutoCorrect Report</title>\n<style>{STYLE}</style>\n</head>\n<body>\n<h1>AutoCorrect Report</h1>\n"
+    );
+    if total.files == 0 {
+        out.push_str("<p>No issues found.</p>\n");
+    } else {
+        out.push_str(&format!(
+            "<p>Files: {}, <span class=\"error\">Error: {}</span>, <span class=\"warning\">Warning: {}</span></p>\n",
+            total.files, total.errors, total.warnings
+        ));
+        out.push_str(&counts_table("Rule", &by_rule, false));
+        out.push_str(&counts_table("Directory", &by_dir, true));
+        out.push_str("<h2>Files</h2>\n");
+        out.push_str(&files);
+    }
+    out.push_str("</body>\n</html>\n");
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::result::json::crate_test_lint_results;
+
+    #[test]
+    fn test_highlight() {
+        assert_eq!(
+            "hello<ins> </ins>你好<ins>。</ins>",
+            highlight("hello你好.", "hello 你好。", "ins")
+        );
+        assert_eq!(
+            "hello你好<del>.</del>",
+            highlight("hello 你好。", "hello你好.", "del")
+        );
+        assert_eq!("&lt;b&gt;<ins> </ins>a", highlight("<b>a", "<b> a", "ins"));
+    }
+
+    #[test]
+    fn test_to_lint_results_html() {
+        let mut lint_results = crate_test_lint_results();
+        lint_results[0].lines[0].rules = vec!["space-word".to_owned(), "fullwidth".to_owned()];
+        lint_results[0].lines[1].rules = vec!["space-word".to_owned()];
+        lint_results[0].lines[1].severity = Severity::Warning;
+
+        let mut other = LintResult::new("");
+        other.filepath = "./README.md".to_owned();
+        other.error = "<parse error>".to_owned();
+        lint_results.push(other);
+
+        let html = to_lint_results_html(lint_results);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(!html.contains("<script") && !html.contains("<link"));
+        assert!(html.contains("<p>Files: 2, <span class=\"error\">Error: 2</span>, <span class=\"warning\">Warning: 1</span></p>"));
+        assert!(html.contains("<tr><td><code>fullwidth</code></td><td class=\"num\">1</td><td class=\"num\">0</td></tr>"));
+        assert!(html.contains("<tr><td><code>space-word</code></td><td class=\"num\">1</td><td class=\"num\">1</td></tr>"));
+        assert!(html.contains("<tr><td><code>.</code></td><td class=\"num\">1</td><td class=\"num\">1</td><td class=\"num\">0</td></tr>"));
+        assert!(html.contains("<tr><td><code>test/foo</code></td><td class=\"num\">1</td><td class=\"num\">1</td><td class=\"num\">1</td></tr>"));
+        assert!(html.contains("<p class=\"error\">1:1 &lt;parse error&gt;</p>"));
+        assert!(html.contains("<td class=\"old\">hello你好<del>.</del></td><td class=\"new\">hello<ins> </ins>你好<ins>。</ins></td>"));
+
+        // The files are sorted by the path
+        assert!(html.find("README.md").unwrap() < html.find("test/foo/bar.rs").unwrap());
+
+        let html = to_lint_results_html(vec![]);
+        assert!(html.contains("<p>No issues found.</p>"));
+    }
+}
@@ -28,6 +28,7 @@ pub(crate) fn crate_test_lint_results() -> Vec<LintResult> {
         new: "hello 你好。".to_owned(),
         old: "hello你好.".to_owned(),
         severity: Severity::Error,
+        ..Default::default()
     });
     lint_result.push(LineResult {
         line: 2,
@@ -35,6 +36,7 @@ pub(crate) fn crate_test_lint_results() -> Vec<LintResult> {
         new: "这是第 2 行".to_owned(),
         old: "这是第2行".to_owned(),
         severity: Severity::Error,
+        ..Default::default()
     });
 
     vec![lint_result]
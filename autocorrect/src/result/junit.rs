@@ -0,0 +1,111 @@
+//! JUnit XML format, for Jenkins, GitLab and the other CI support the test reports.
+//!
+//! Each file is a testcase, and each line result is a failure of it.
+use super::{xml, LintResult, Severity};
+
+#[doc(hidden)]
+pub fn to_lint_results_junit(lint_results: Vec<LintResult>) -> String {
+    let mut testcases = String::new();
+    let mut failures = 0;
+    let mut errors = 0;
+
+    for lint_result in lint_results.iter() {
+        let filepath = xml::escape(&lint_result.filepath.replace("./", ""));
+        let lines = lint_result
+            .lines
+            .iter()
+            .filter(|line_result| !line_result.severity.is_pass())
+            .collect::<Vec<_>>();
+
+        if lint_result.has_error() {
+            errors += 1;
+        } else if !lines.is_empty() {
+            failures += 1;
+        }
+
+        testcases.push_str(&format!(
+            "<testcase name=\"{filepath}\" classname=\"autocorrect\" file=\"{filepath}\">\n"
+        ));
+
+        if lint_result.has_error() {
+            testcases.push_str(&format!(
+                "<error message=\"{}\"></error>\n",
+                xml::escape(&lint_result.error)
+            ));
+        }
+
+        for line_result in lines {
+            let kind = match line_result.severity {
+                Severity::Warning => "warning",
+                _ => "error",
+            };
+            let mut message = format!("{}:{}", line_result.line, line_result.col);
+            if !line_result.rules.is_empty() {
+                message.push_str(&format!(" {}", line_result.rules.join(",")));
+            }
+            if !line_result.message.is_empty() {
+                message.push_str(&format!(" {}", line_result.message));
+            }
+
+            testcases.push_str(&format!(
+                "<failure type=\"{}\" message=\"{}\">{}</failure>\n",
+                kind,
+                xml::escape(&message),
+                xml::escape(&format!(
+                    "{}:{}:{}\n- {}\n+ {}",
+                    lint_result.filepath.replace("./", ""),
+                    line_result.line,
+                    line_result.col,
+                    line_result.old,
+                    line_result.new
+                )),
+            ));
+        }
+
+        testcases.push_str("</testcase>\n");
+    }
+
+    let tests = lint_results.len();
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="AutoCorrect" tests="{tests}" failures="{failures}" errors="{errors}">
+<testsuite name="AutoCorrect" tests="{tests}" failures="{failures}" errors="{errors}">
+{testcases}</testsuite>
+</testsuites>
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::result::json::crate_test_lint_results;
+
+    #[test]
+    fn test_to_lint_results_junit() {
+        let mut lint_results = crate_test_lint_results();
+        lint_results[0].lines[0].rules = vec!["space-word".to_owned()];
+        lint_results[0].lines[1].severity = Severity::Warning;
+
+        let mut error_result = LintResult::new("");
+        error_result.filepath = "./foo & bar.md".to_owned();
+        error_result.error = "Parse error".to_owned();
+        lint_results.push(error_result);
+
+        let expected = indoc::indoc! {r#"
+        <?xml version="1.0" encoding="UTF-8"?>
+        <testsuites name="AutoCorrect" tests="2" failures="1" errors="1">
+        <testsuite name="AutoCorrect" tests="2" failures="1" errors="1">
+        <testcase name="test/foo/bar.rs" classname="autocorrect" file="test/foo/bar.rs">
+        <failure type="error" message="1:1 space-word">test/foo/bar.rs:1:1&#10;- hello你好.&#10;+ hello 你好。</failure>
+        <failure type="warning" message="2:1">test/foo/bar.rs:2:1&#10;- 这是第2行&#10;+ 这是第 2 行</failure>
+        </testcase>
+        <testcase name="foo &amp; bar.md" classname="autocorrect" file="foo &amp; bar.md">
+        <error message="Parse error"></error>
+        </testcase>
+        </testsuite>
+        </testsuites>
+        "#};
+        assert_eq!(expected, to_lint_results_junit(lint_results));
+    }
+}
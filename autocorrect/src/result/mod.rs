@@ -1,9 +1,18 @@
+pub mod checkstyle;
+pub mod github;
+pub mod gitlab;
+pub mod html;
 pub mod json;
+pub mod junit;
+pub mod patch;
 pub mod rdjson;
+mod xml;
 use serde::{Deserialize, Serialize};
 use serde_repr::*;
 
 use crate::config::toggle;
+use crate::Config;
+use owo_colors::OwoColorize;
 
 #[derive(Serialize_repr, Deserialize_repr, PartialEq, Eq, Debug, Default, Clone, Copy)]
 #[repr(u8)]
@@ -28,7 +37,7 @@ impl Severity {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Default)]
 pub struct LineResult {
     #[serde(rename(serialize = "l"))]
     pub line: usize,
@@ -37,17 +46,60 @@ pub struct LineResult {
     pub new: String,
     pub old: String,
     pub severity: Severity,
+    /// The description of the lint, e.g.: "Prefer `資訊` over `信息`"
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub message: String,
+    /// The names of the rules that changed the line, e.g.: `["space-word"]`
+    ///
+    /// Not in the JSON output, to keep the output of `--format json` stable.
+    #[serde(skip)]
+    pub rules: Vec<String>,
+    /// The line where the parsed node (e.g.: the string, the comment) of this line starts, `0` if unknown.
+    ///
+    /// A line is inside a multi-line node if it is not the same as the `line`,
+    /// e.g.: a multi-line string, so a comment can't be inserted before the line.
+    #[serde(skip)]
+    pub node_line: usize,
+}
+
+/// The grammar parse error of a file, the file can't be formatted or linted when it has a parse error.
+#[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub col: usize,
+    /// The grammar name, e.g.: `markdown`, `html`
+    pub grammar: String,
+    /// The expected rules of the grammar at the position, e.g.: `["string", "object"]`
+    pub expected: Vec<String>,
+    /// The message of the parser, e.g.: "expected string or object"
+    pub message: String,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} parse error: {}", self.grammar, self.message)
+    }
 }
 
 pub trait Results {
     fn push(&mut self, line_result: LineResult);
     fn ignore(&mut self, str: &str);
     fn error(&mut self, err: &str);
+    fn parse_error(&mut self, err: ParseError) {
+        self.error(&err.to_string());
+    }
     #[allow(unused)]
     fn to_string(&self) -> String;
     fn is_lint(&self) -> bool;
     fn get_toggle(&self) -> &toggle::Toggle;
     fn toggle_mut(&mut self) -> &mut toggle::Toggle;
+    fn get_line_toggles(&self) -> &toggle::LineToggles;
+    fn line_toggles_mut(&mut self) -> &mut toggle::LineToggles;
+
+    /// The directives to report, `None` if not to report, see `reportUnusedDisableDirectives` config.
+    fn directives_mut(&mut self) -> Option<&mut Vec<toggle::Directive>> {
+        None
+    }
 
     /// Move and save current line,col return the previous line number
     fn move_cursor(&mut self, part: &str) -> (usize, usize);
@@ -62,6 +114,21 @@ pub trait Results {
         *self.toggle_mut() = new_toggle.clone();
     }
 
+    /// Toggle AutoCorrect for a single line, e.g.: `autocorrect-disable-next-line`
+    fn toggle_line(&mut self, line: usize, new_toggle: &toggle::Toggle) {
+        self.line_toggles_mut().insert(line, new_toggle.clone());
+    }
+
+    /// The toggle of the line, merged the line toggle into the current toggle.
+    fn get_line_toggle(&self, line: usize) -> toggle::Toggle {
+        let mut toggle = self.get_toggle().clone();
+        if let Some(line_toggle) = self.get_line_toggles().get(line) {
+            toggle.merge(line_toggle.clone());
+        }
+
+        toggle
+    }
+
     fn toggle_merge_for_codeblock(&mut self) {
         self.toggle_mut()
             .merge(toggle::Toggle::disable(vec!["halfwidth-punctuation"]));
@@ -83,6 +150,8 @@ pub struct FormatResult {
     pub enable: bool,
     #[serde(skip)]
     pub toggle: toggle::Toggle,
+    #[serde(skip)]
+    pub line_toggles: toggle::LineToggles,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -92,10 +161,17 @@ pub struct LintResult {
     pub filepath: String,
     pub lines: Vec<LineResult>,
     pub error: String,
+    /// The structured parse error, `error` is the message of it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parse_error: Option<ParseError>,
     #[serde(skip)]
     pub enable: bool,
     #[serde(skip)]
     pub toggle: toggle::Toggle,
+    #[serde(skip)]
+    pub line_toggles: toggle::LineToggles,
+    #[serde(skip)]
+    pub directives: Vec<toggle::Directive>,
     // For store line number in loop
     #[serde(skip)]
     line: usize,
@@ -112,6 +188,7 @@ impl FormatResult {
             error: String::from(""),
             enable: true,
             toggle: toggle::Toggle::default(),
+            line_toggles: toggle::LineToggles::default(),
         }
     }
 
@@ -153,6 +230,14 @@ impl Results for FormatResult {
         &self.toggle
     }
 
+    fn get_line_toggles(&self) -> &toggle::LineToggles {
+        &self.line_toggles
+    }
+
+    fn line_toggles_mut(&mut self) -> &mut toggle::LineToggles {
+        &mut self.line_toggles
+    }
+
     fn move_cursor(&mut self, _part: &str) -> (usize, usize) {
         (0, 0)
     }
@@ -167,8 +252,11 @@ impl LintResult {
             raw: String::from(raw),
             lines: Vec::new(),
             error: String::from(""),
+            parse_error: None,
             enable: true,
             toggle: toggle::Toggle::default(),
+            line_toggles: toggle::LineToggles::default(),
+            directives: vec![],
         }
     }
 
@@ -193,8 +281,26 @@ impl LintResult {
         let mut out = String::new();
         let filepath = self.filepath.replace("./", "");
 
+        if self.has_error() {
+            let (line, col) = self.error_position();
+            out.push_str(&format!(
+                "{}:{}:{} {}\n",
+                filepath,
+                line,
+                col,
+                self.error.red()
+            ));
+        }
+
         for line in self.lines.iter() {
-            out.push_str(&format!("{}:{}:{}\n", filepath, line.line, line.col));
+            if line.message.is_empty() {
+                out.push_str(&format!("{}:{}:{}\n", filepath, line.line, line.col));
+            } else {
+                out.push_str(&format!(
+                    "{}:{}:{} {}\n",
+                    filepath, line.line, line.col, line.message
+                ));
+            }
 
             let out_str = crate::diff::diff_line_result(line, no_diff_bg_color);
             out.push_str(&out_str);
@@ -208,6 +314,14 @@ impl LintResult {
         !self.error.is_empty()
     }
 
+    /// The line and col of the parse error, or the beginning of the file if unknown.
+    pub fn error_position(&self) -> (usize, usize) {
+        self.parse_error
+            .as_ref()
+            .map(|err| (err.line, err.col))
+            .unwrap_or((1, 1))
+    }
+
     /// Return number of errors
     pub fn errors_count(&self) -> usize {
         self.lines.iter().filter(|l| l.severity.is_error()).count()
@@ -236,6 +350,11 @@ impl Results for LintResult {
         self.error = String::from(err);
     }
 
+    fn parse_error(&mut self, err: ParseError) {
+        self.error = err.to_string();
+        self.parse_error = Some(err);
+    }
+
     fn to_string(&self) -> String {
         String::from("")
     }
@@ -248,6 +367,22 @@ impl Results for LintResult {
         &self.toggle
     }
 
+    fn get_line_toggles(&self) -> &toggle::LineToggles {
+        &self.line_toggles
+    }
+
+    fn line_toggles_mut(&mut self) -> &mut toggle::LineToggles {
+        &mut self.line_toggles
+    }
+
+    fn directives_mut(&mut self) -> Option<&mut Vec<toggle::Directive>> {
+        if Config::current().report_unused_disable_directives == Some(true) {
+            Some(&mut self.directives)
+        } else {
+            None
+        }
+    }
+
     fn toggle_mut(&mut self) -> &mut toggle::Toggle {
         &mut self.toggle
     }
@@ -307,6 +442,29 @@ fn line_col(part: &str) -> (usize, usize, bool) {
     (line_col.0, line_col.1, has_new_line)
 }
 
+/// Output the lint results as the report in the `format`, e.g.: `json`, `rdjson`, `github`, `gitlab`, `checkstyle`, `junit`, `html`.
+///
+/// ```
+/// let result = autocorrect::lint_for("hello你好.", "text");
+/// let report = autocorrect::to_lint_results_report(vec![result], "checkstyle").unwrap();
+/// assert!(report.contains("<checkstyle"));
+/// ```
+pub fn to_lint_results_report(
+    lint_results: Vec<LintResult>,
+    format: &str,
+) -> Result<String, String> {
+    match format {
+        "json" => Ok(json::to_lint_results_json(lint_results)),
+        "rdjson" => Ok(rdjson::to_lint_results_rdjson(lint_results)),
+        "github" => Ok(github::to_lint_results_github(lint_results)),
+        "gitlab" => Ok(gitlab::to_lint_results_gitlab(lint_results)),
+        "checkstyle" => Ok(checkstyle::to_lint_results_checkstyle(lint_results)),
+        "junit" => Ok(junit::to_lint_results_junit(lint_results)),
+        "html" => Ok(html::to_lint_results_html(lint_results)),
+        _ => Err(format!("Unsupported report format: {format}")),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
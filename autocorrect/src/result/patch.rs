@@ -0,0 +1,176 @@
+//! Unified diff, can be applied by `git apply` or `patch -p1`.
+//! https://www.gnu.org/software/diffutils/manual/html_node/Unified-Format.html
+
+/// The number of the unchanged lines around the changes.
+const CONTEXT_LINES: usize = 3;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Generate the unified diff of a file with the `a/` and `b/` prefixes, returns empty if not changed.
+///
+/// ```
+/// let patch = autocorrect::patch::to_patch("foo.md", "你好hello\n", "你好 hello\n");
+/// assert_eq!("--- a/foo.md\n+++ b/foo.md\n@@ -1 +1 @@\n-你好hello\n+你好 hello\n", patch);
+/// ```
+pub fn to_patch(filepath: &str, old: &str, new: &str) -> String {
+    if old == new {
+        return String::new();
+    }
+
+    // Keep the line endings, so the change of the newline at end of file is also a change.
+    let old_lines = old.split_inclusive('\n').collect::<Vec<_>>();
+    let new_lines = new.split_inclusive('\n').collect::<Vec<_>>();
+    let ops = diff::slice(&old_lines, &new_lines)
+        .into_iter()
+        .map(|result| match result {
+            diff::Result::Both(line, _) => (Op::Equal, *line),
+            diff::Result::Left(line) => (Op::Delete, *line),
+            diff::Result::Right(line) => (Op::Insert, *line),
+        })
+        .collect::<Vec<_>>();
+
+    let filepath = filepath.trim_start_matches("./");
+    let mut out = format!("--- a/{filepath}\n+++ b/{filepath}\n");
+    for (start, end) in hunks(&ops) {
+        out.push_str(&hunk_header(&ops, start, end));
+        for (op, line) in &ops[start..end] {
+            out.push(match op {
+                Op::Equal => ' ',
+                Op::Delete => '-',
+                Op::Insert => '+',
+            });
+            out.push_str(line);
+            if !line.ends_with('\n') {
+                out.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+    }
+
+    out
+}
+
+/// Group the changes into the hunks, returns the ranges of the ops.
+fn hunks(ops: &[(Op, &str)]) -> Vec<(usize, usize)> {
+    let mut hunks: Vec<(usize, usize)> = vec![];
+    for i in (0..ops.len()).filter(|i| ops[*i].0 != Op::Equal) {
+        let start = i.saturating_sub(CONTEXT_LINES);
+        let end = (i + 1 + CONTEXT_LINES).min(ops.len());
+
+        match hunks.last_mut() {
+            // Merge into the last hunk, if the contexts are overlapped or adjacent
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    hunks
+}
+
+fn hunk_header(ops: &[(Op, &str)], start: usize, end: usize) -> String {
+    let count = |range: &[(Op, &str)], skip: Op| range.iter().filter(|(op, _)| *op != skip).count();
+    let old_before = count(&ops[..start], Op::Insert);
+    let new_before = count(&ops[..start], Op::Delete);
+    let old_count = count(&ops[start..end], Op::Insert);
+    let new_count = count(&ops[start..end], Op::Delete);
+
+    format!(
+        "@@ -{} +{} @@\n",
+        hunk_range(old_before, old_count),
+        hunk_range(new_before, new_count)
+    )
+}
+
+/// The range of the hunk header, the start is the line before the hunk if the hunk is empty.
+fn hunk_range(before: usize, count: usize) -> String {
+    match count {
+        0 => format!("{before},0"),
+        1 => format!("{}", before + 1),
+        _ => format!("{},{}", before + 1, count),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_to_patch() {
+        assert_eq!("", to_patch("foo.md", "你好\n", "你好\n"));
+
+        let old = "1\n2\n3\n4\n你好hello\n6\n7\n8\n9\n10\n11\n12\n世界world\n14\n";
+        let new = "1\n2\n3\n4\n你好 hello\n6\n7\n8\n9\n10\n11\n12\n世界 world\n14\n";
+        let expected = indoc! {r#"
+        --- a/foo/bar.md
+        +++ b/foo/bar.md
+        @@ -2,7 +2,7 @@
+         2
+         3
+         4
+        -你好hello
+        +你好 hello
+         6
+         7
+         8
+        @@ -10,5 +10,5 @@
+         10
+         11
+         12
+        -世界world
+        +世界 world
+         14
+        "#};
+        assert_eq!(expected, to_patch("./foo/bar.md", old, new));
+
+        // The hunks are merged when the contexts are overlapped
+        let old = "你好hello\n2\n3\n4\n5\n6\n世界world\n";
+        let new = "你好 hello\n2\n3\n4\n5\n6\n世界 world\n";
+        let expected = indoc! {r#"
+        --- a/foo.md
+        +++ b/foo.md
+        @@ -1,7 +1,7 @@
+        -你好hello
+        +你好 hello
+         2
+         3
+         4
+         5
+         6
+        -世界world
+        +世界 world
+        "#};
+        assert_eq!(expected, to_patch("foo.md", old, new));
+    }
+
+    #[test]
+    fn test_to_patch_without_newline_at_end() {
+        let expected = indoc! {r#"
+        --- a/foo.md
+        +++ b/foo.md
+        @@ -1,2 +1,2 @@
+         第一行
+        -你好hello
+        \ No newline at end of file
+        +你好 hello
+        \ No newline at end of file
+        "#};
+        assert_eq!(
+            expected,
+            to_patch("foo.md", "第一行\n你好hello", "第一行\n你好 hello")
+        );
+
+        let expected = indoc! {r#"
+        --- a/foo.md
+        +++ b/foo.md
+        @@ -0,0 +1 @@
+        +你好
+        "#};
+        assert_eq!(expected, to_patch("foo.md", "", "你好\n"));
+    }
+}
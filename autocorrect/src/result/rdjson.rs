@@ -58,6 +58,28 @@ fn to_severity_str(severity: super::Severity) -> String {
 #[doc(hidden)]
 pub(crate) fn to_rdjson_diagnostics(lint_result: &LintResult) -> Vec<String> {
     let mut diagnostics = vec![];
+    if lint_result.has_error() {
+        let (line, column) = lint_result.error_position();
+        let rdf_diagnostic = RdfDiagnostic {
+            message: lint_result.error.clone(),
+            location: RdfLocation {
+                path: lint_result.filepath.replace("./", ""),
+                range: RdfRange {
+                    start: Some(RdfLineColumn { line, column }),
+                    end: None,
+                },
+            },
+            severity: to_severity_str(super::Severity::Error),
+            code: RdfCode {
+                value: Some("AutoCorrect".to_owned()),
+                url: "https://github.com/huacnlee/autocorrect".to_owned(),
+            },
+            suggestions: vec![],
+        };
+
+        diagnostics.push(serde_json::to_string(&rdf_diagnostic).unwrap())
+    }
+
     lint_result.lines.iter().for_each(|line_result| {
         let start: RdfLineColumn = RdfLineColumn {
             line: line_result.line,
@@ -69,7 +91,7 @@ pub(crate) fn to_rdjson_diagnostics(lint_result: &LintResult) -> Vec<String> {
         };
 
         let rdf_diagnostic: RdfDiagnostic = RdfDiagnostic {
-            message: "".to_owned(),
+            message: line_result.message.clone(),
             location: RdfLocation {
                 path: lint_result.filepath.replace("./", ""),
                 range: RdfRange {
@@ -121,4 +143,23 @@ mod tests {
         }
         assert_json_eq!(expected, rdjson);
     }
+
+    #[test]
+    fn test_to_lint_results_rdjson_with_parse_error() {
+        use crate::result::{LintResult, ParseError, Results};
+
+        let mut lint_result = LintResult::new("");
+        lint_result.filepath = "./foo.vue".to_owned();
+        lint_result.parse_error(ParseError {
+            line: 3,
+            col: 5,
+            grammar: "html".to_owned(),
+            expected: vec!["tag".to_owned()],
+            message: "expected tag".to_owned(),
+        });
+        let rdjson = super::to_lint_results_rdjson(vec![lint_result]);
+
+        let expected = r#"{"source":{"name":"AutoCorrect Lint","url": "https://github.com/huacnlee/autocorrect"},"diagnostics": [{"message":"html parse error: expected tag","severity":"ERROR","code":{"value":"AutoCorrect","url":"https://github.com/huacnlee/autocorrect"},"location":{"path":"foo.vue","range":{"start":{"line":3,"column":5},"end":null}},"suggestions":[]}]}"#;
+        assert_json_eq!(expected, rdjson);
+    }
 }
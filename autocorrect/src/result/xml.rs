@@ -0,0 +1,40 @@
+//! Helpers for the XML reporters.
+
+/// Escape the text for the XML attribute value and text content.
+///
+/// The control chars are not allowed in XML 1.0 (even as the char reference), so they are replaced by `U+FFFD`.
+pub(crate) fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\n' => out.push_str("&#10;"),
+            '\r' => out.push_str("&#13;"),
+            '\t' => out.push_str("&#9;"),
+            '\u{0}'..='\u{1F}' | '\u{FFFE}' | '\u{FFFF}' => out.push('\u{FFFD}'),
+            _ => out.push(c),
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            "&lt;b&gt;你好&lt;/b&gt; &amp; &quot;世界&quot; &apos;a&apos;",
+            super::escape(r#"<b>你好</b> & "世界" 'a'"#)
+        );
+        assert_eq!(
+            "第 1 行&#10;第 2 行&#9;",
+            super::escape("第 1 行\n第 2 行\t")
+        );
+        assert_eq!("a\u{FFFD}b\u{FFFD}", super::escape("a\u{1}b\u{1B}"));
+    }
+}
@@ -0,0 +1,42 @@
+/// The context of the text in the document.
+///
+/// The rules can be enabled or disabled in each context by the `context` config, e.g.:
+///
+/// ```yml
+/// context:
+///   string-literal:
+///     fullwidth: 0
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Context {
+    /// Normal text, e.g.: paragraph in Markdown, text in HTML.
+    #[default]
+    Prose,
+    /// The text at the end of a heading, e.g.: `# Heading` in Markdown, `\section{Heading}` in LaTeX.
+    Heading,
+    /// Comment in code or markup, e.g.: `// Comment`, `<!-- Comment -->`.
+    Comment,
+    /// String literal in code, e.g.: `"Hello"` in JavaScript.
+    StringLiteral,
+    /// The text of a link, e.g.: `[Link](/link)` in Markdown.
+    LinkText,
+    /// The text in a table cell, e.g.: `| Cell |` in Markdown.
+    TableCell,
+    /// The value of front matter, e.g.: `title: Hello` in Markdown.
+    FrontMatterValue,
+}
+
+impl Context {
+    /// The name of the context in the `context` config.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Context::Prose => "prose",
+            Context::Heading => "heading",
+            Context::Comment => "comment",
+            Context::StringLiteral => "string-literal",
+            Context::LinkText => "link-text",
+            Context::TableCell => "table-cell",
+            Context::FrontMatterValue => "front-matter-value",
+        }
+    }
+}
@@ -2,8 +2,8 @@
 use regex::Regex;
 use std::{borrow::Cow, collections::HashMap};
 
-const SPECIAL_PUNCTUATIONS: &str = "[.:!]([ ]*)";
-const NORMAL_PUNCTUATIONS: &str = "[,?]([ ]*)";
+const SPECIAL_PUNCTUATIONS: &str = "[ ]*[.:!]([ ]*)";
+const NORMAL_PUNCTUATIONS: &str = "[ ]*[,?]([ ]*)";
 
 lazy_static! {
     static ref FULLWIDTH_MAPS: HashMap<&'static str, &'static str> = map!(
@@ -23,7 +23,7 @@ lazy_static! {
     static ref PUNCTUATION_WITH_RIGHT_CJK_RE: Regex =
         regexp!("{}{}", r"[\p{CJ}]+", NORMAL_PUNCTUATIONS);
     static ref PUNCTUATION_WITH_SEPEICAL_CJK_RE: Regex =
-        regexp!("{}{}{}", r"[\p{CJ}]+", SPECIAL_PUNCTUATIONS, r"[\p{CJ}]+");
+        regexp!("{}{}{}", r"[\p{CJ}]+", SPECIAL_PUNCTUATIONS, r"[\p{CJK}]+");
     static ref PUNCTUATION_WITH_SEPEICAL_LAST_CJK_RE: Regex =
         regexp!("{}{}{}", r"[\p{CJ}]+", SPECIAL_PUNCTUATIONS, r#"["']?$"#);
     static ref PUNCTUATIONS_RE: Regex =
@@ -41,9 +41,13 @@ pub fn format(text: &str) -> Cow<'_, str> {
 
     let mut result = Cow::Borrowed(text);
     for pattern in &patterns {
-        if let Cow::Owned(new_text) = pattern.replace_all(&result, |cap: &regex::Captures| {
+        // Replace again for the overlapped matches, e.g.: `中:文:中`, the `文` is in the first match
+        while let Cow::Owned(new_text) = pattern.replace_all(&result, |cap: &regex::Captures| {
             fullwidth_replace_part(&cap[0])
         }) {
+            if new_text == result {
+                break;
+            }
             result = Cow::Owned(new_text);
         }
     }
@@ -101,7 +105,11 @@ mod tests {
             "你好,这是一个句子." => "你好，这是一个句子。",
             "你好,這是一個句子." => "你好，這是一個句子。",
             "でもっと多くのことができるようになります.そんな新機能の数々をさっそく体験してみましょう." => "でもっと多くのことができるようになります。そんな新機能の数々をさっそく体験してみましょう。",
-            "근면, 검소, 협동은 우리 겨레의 미덕이다." => "근면, 검소, 협동은 우리 겨레의 미덕이다."
+            "근면, 검소, 협동은 우리 겨레의 미덕이다." => "근면, 검소, 협동은 우리 겨레의 미덕이다.",
+            "中文!한국어" => "中文！한국어",
+            "中文! 한국어" => "中文！한국어",
+            "にほんご:測試:にほんご" => "にほんご：測試：にほんご",
+            "中,文,中" => "中，文，中"
         );
 
         assert_cases(cases);
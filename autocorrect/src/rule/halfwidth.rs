@@ -56,7 +56,8 @@ impl ReplaceRule {
 }
 
 lazy_static! {
-    static ref HALF_TIME_RE: Regex = regexp!("{}", r"(\d)(：)(\d)");
+    // The spaces near `：` are removed by `no-space-fullwidth` anyway, e.g.: `16 ：32`
+    static ref HALF_TIME_RE: Regex = regexp!("{}", r"(\d)[ ]*(：)[ ]*(\d)");
     // More than 2 words and leading with words
     static ref ENGLISH_RE: Regex = regexp!("{}", r#"([\w]+[ ,.'?!&:]+[\w]+)"#);
     static ref START_WITH_WORD_RE: Regex = regexp!("{}", r#"^\s*[\w]+"#);
@@ -156,10 +157,10 @@ pub fn format_word(text: &str) -> Cow<'_, str> {
         .collect::<String>();
 
     if changed {
-        let out = HALF_TIME_RE.replace_all(&out, |cap: &regex::Captures| cap[0].replace('：', ":"));
+        let out = HALF_TIME_RE.replace_all(&out, "${1}:${3}");
         Cow::Owned(out.into_owned())
     } else {
-        HALF_TIME_RE.replace_all(text, |cap: &regex::Captures| cap[0].replace('：', ":"))
+        HALF_TIME_RE.replace_all(text, "${1}:${3}")
     }
 }
 
@@ -270,6 +271,9 @@ mod tests {
             "他说：我们将在16:32分出发去CBD中心。",
             format_word("他说：我们将在１６：３２分出发去ＣＢＤ中心。")
         );
+        assert_eq!("在 16:32 出发", format_word("在 16 ：32 出发"));
+        assert_eq!("在 16:32 出发", format_word("在 16： 32 出发"));
+        assert_eq!("在 16:32 出发", format_word("在 １６ ： ３２ 出发"));
 
         // Fullwidth space
         assert_eq!(
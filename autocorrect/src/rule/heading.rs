@@ -0,0 +1,61 @@
+// autocorrect: false
+use std::borrow::Cow;
+
+use regex::Regex;
+
+lazy_static! {
+    /// The trailing fullwidth punctuations, before the whitespace or the `}` of LaTeX.
+    static ref TRAILING_PUNCTUATION_RE: Regex = regexp!(r"[。：；，、]+(\s*\}?\s*)$");
+    /// The heading marker without space, e.g.: `##标题` in Markdown, `==标题` in AsciiDoc.
+    static ref MARKER_WITHOUT_SPACE_RE: Regex = regexp!(r"^([#]{1,6}|[=]{1,6})(\p{CJK})");
+}
+
+/// Remove the trailing fullwidth punctuations in heading, e.g.: `## 总结：` -> `## 总结`
+pub fn format_punctuation(text: &str) -> Cow<'_, str> {
+    TRAILING_PUNCTUATION_RE.replace(text, "$1")
+}
+
+/// Add a space after the heading marker, e.g.: `##标题` -> `## 标题`
+pub fn format_space(text: &str) -> Cow<'_, str> {
+    MARKER_WITHOUT_SPACE_RE.replace(text, "$1 $2")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_punctuation() {
+        let cases = map! {
+            "总结：" => "总结",
+            "这是一个标题。" => "这是一个标题",
+            "这是一个标题。 " => "这是一个标题 ",
+            "{这是一个标题。}" => "{这是一个标题}",
+            "标题；、" => "标题",
+            "为什么？" => "为什么？",
+            "注意！" => "注意！",
+            "《标题》" => "《标题》",
+            "标题：内容" => "标题：内容",
+        };
+
+        for (source, expected) in cases {
+            assert_eq!(expected, format_punctuation(source), "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_format_space() {
+        let cases = map! {
+            "#标题" => "# 标题",
+            "###标题" => "### 标题",
+            "==标题" => "== 标题",
+            "# 标题" => "# 标题",
+            "#123" => "#123",
+            "标题" => "标题",
+        };
+
+        for (source, expected) in cases {
+            assert_eq!(expected, format_space(source), "{}", source);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use serde::Serialize;
+
+use crate::config::{SeverityMode, DEFAULT_CONFIG};
+
+use super::{registry, Phase, Rule, RuleTrait, AFTER_RULES, BEFORE_RULES, CONTEXT_RULES, RULES};
+
+/// The metadata of a rule, for `autocorrect rules`, `autocorrect explain` and the LSP hover.
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleInfo {
+    pub name: String,
+    pub description: String,
+    pub phase: Phase,
+    /// The severity in the default config for the built-in rules, or the rule's own default for the custom rules.
+    pub default_severity: SeverityMode,
+    /// The rule only applies in the context, e.g.: `heading`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<&'static str>,
+    pub examples: Vec<RuleExample>,
+    /// The config to run the examples, e.g.: `locale: zh-TW` for `terminology`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example_config: Option<String>,
+    /// Is a built-in rule, or a custom rule registered by `Engine::register_rule`.
+    pub builtin: bool,
+}
+
+/// An example of the rule, the `bad` text will be formatted to the `good` text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RuleExample {
+    pub bad: String,
+    pub good: String,
+}
+
+impl RuleInfo {
+    fn new<R: RuleTrait + ?Sized>(rule: &R, phase: Phase) -> Self {
+        Self {
+            name: rule.name().to_owned(),
+            description: rule.description().to_owned(),
+            phase,
+            default_severity: rule.default_severity(),
+            context: None,
+            examples: rule
+                .examples()
+                .iter()
+                .map(|(bad, good)| RuleExample {
+                    bad: bad.to_string(),
+                    good: good.to_string(),
+                })
+                .collect(),
+            example_config: None,
+            builtin: false,
+        }
+    }
+
+    fn builtin(rule: &Rule, phase: Phase) -> Self {
+        Self {
+            default_severity: DEFAULT_CONFIG
+                .rules
+                .get(&rule.name)
+                .copied()
+                .unwrap_or_default(),
+            example_config: rule.example_config.map(|config| config.to_owned()),
+            builtin: true,
+            ..Self::new(rule, phase)
+        }
+    }
+}
+
+/// Get the metadata of all rules, the built-in rules first, then the custom rules.
+pub fn rules() -> Vec<RuleInfo> {
+    let mut rules = vec![];
+    rules.extend(
+        BEFORE_RULES
+            .iter()
+            .chain(RULES.iter())
+            .map(|r| RuleInfo::builtin(r, Phase::Part)),
+    );
+    rules.extend(
+        AFTER_RULES
+            .iter()
+            .map(|r| RuleInfo::builtin(r, Phase::After)),
+    );
+    rules.extend(CONTEXT_RULES.iter().map(|(context, r)| RuleInfo {
+        context: Some(context.name()),
+        ..RuleInfo::builtin(r, Phase::After)
+    }));
+
+    for phase in [Phase::Part, Phase::After] {
+        rules.extend(
+            registry::custom_rules(phase)
+                .iter()
+                .map(|r| RuleInfo::new(r.as_ref(), phase)),
+        );
+    }
+
+    rules
+}
+
+/// Get the metadata of a rule by name.
+pub fn rule_info(name: &str) -> Option<RuleInfo> {
+    rules().into_iter().find(|r| r.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rules() {
+        let rules = rules();
+        assert_eq!(
+            super::super::default_rule_names().len(),
+            rules.iter().filter(|r| r.builtin).count()
+        );
+
+        for rule in rules.iter().filter(|r| r.builtin) {
+            assert!(!rule.description.is_empty(), "{}", rule.name);
+            assert!(!rule.examples.is_empty(), "{}", rule.name);
+        }
+
+        let rule = rule_info("space-dash").unwrap();
+        assert_eq!(Phase::Part, rule.phase);
+        assert_eq!(SeverityMode::Error, rule.default_severity);
+        assert_eq!(None, rule.context);
+
+        let rule = rule_info("heading-space").unwrap();
+        assert_eq!(Phase::After, rule.phase);
+        assert_eq!(SeverityMode::Off, rule.default_severity);
+        assert_eq!(Some("heading"), rule.context);
+
+        assert!(rule_info("unknown").is_none());
+    }
+
+    #[test]
+    fn test_rule_examples() {
+        crate::config::setup_test();
+
+        let rules = BEFORE_RULES
+            .iter()
+            .chain(RULES.iter())
+            .chain(AFTER_RULES.iter())
+            .chain(CONTEXT_RULES.iter().map(|(_, r)| r))
+            // Depends on the `locale` of the example config
+            .filter(|r| r.name != "terminology");
+
+        for rule in rules {
+            for (bad, good) in rule.examples {
+                assert_eq!(*good, (rule.format_fn)(bad), "{}", rule.name);
+            }
+        }
+    }
+}
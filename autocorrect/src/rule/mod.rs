@@ -1,8 +1,17 @@
 // autocorrect: false
+mod context;
 mod fullwidth;
+mod heading;
+mod info;
+pub(crate) mod placeholder;
+mod registry;
 #[allow(clippy::module_inception)]
 mod rule;
+mod script;
+mod sentence;
 mod strategery;
+mod terminology;
+mod whitespace;
 mod word;
 
 pub mod halfwidth;
@@ -13,39 +22,130 @@ use std::{borrow::Cow, collections::HashMap};
 use regex::Regex;
 use rule::{Rule, RuleResult};
 
+use crate::config::Pipeline;
 use crate::result::Severity;
+pub use context::Context;
+pub use info::{rule_info, rules, RuleExample, RuleInfo};
+pub use registry::{Engine, Phase, Rule as RuleTrait};
+pub(crate) use script::{with_document_script, Script};
 
 lazy_static! {
+    /// The rules apply to the whole text before split into the parts.
+    static ref BEFORE_RULES: Vec<Rule> = vec![
+        // Rule: normalize-whitespace
+        Rule::new("normalize-whitespace", whitespace::format).with_description(
+            "Normalize the ideographic space U+3000, NBSP and remove the invisible chars (e.g.: zero width space, BOM) near the CJK.",
+            &[("你好\u{3000}世界", "你好 世界"), ("你好\u{200B}世界", "你好世界")],
+        ),
+    ];
+
     static ref RULES: Vec<Rule> = vec![
         // Rule: space-word
-        Rule::new("space-word", word::format_space_word),
+        Rule::new("space-word", word::format_space_word).with_description(
+            "Add space between CJK (Chinese, Japanese, Korean) and English words or numbers.",
+            &[("Hello世界", "Hello 世界"), ("第1章", "第 1 章")],
+        ),
         // Rule: space-punctuation
-        Rule::new("space-punctuation", word::format_space_punctuation),
+        Rule::new("space-punctuation", word::format_space_punctuation).with_description(
+            "Add space between some punctuations (e.g.: `|`, `+`, `!`) and CJK.",
+            &[("你好+世界", "你好 + 世界")],
+        ),
         // Rule: space-bracket
-        Rule::new("space-bracket", word::format_space_bracket),
+        Rule::new("space-bracket", word::format_space_bracket).with_description(
+            "Add space between brackets (), [] and CJK.",
+            &[("打开(文件)列表", "打开 (文件) 列表")],
+        ),
         // Rule: space-dash
-        Rule::new("space-dash", word::format_space_dash),
+        Rule::new("space-dash", word::format_space_dash).with_description(
+            "Add space between dash `-` and CJK.",
+            &[("中文-英文", "中文 - 英文")],
+        ),
         // Rule: space-backticks
-        Rule::new("space-backticks", word::format_space_backticks),
+        Rule::new("space-backticks", word::format_space_backticks).with_description(
+            "Add space between backticks `` and CJK.",
+            &[("使用`code`标记", "使用 `code` 标记")],
+        ),
         // Rule: space-dollar
-        Rule::new("space-dollar", word::format_space_dollar),
+        Rule::new("space-dollar", word::format_space_dollar).with_description(
+            "Add space between dollar $ and CJK.",
+            &[("公式$x$表示", "公式 $x$ 表示")],
+        ),
         // Rule: fullwidth
-        Rule::new("fullwidth", fullwidth::format),
+        Rule::new("fullwidth", fullwidth::format).with_description(
+            "Convert the halfwidth punctuations to fullwidth in CJK.",
+            &[("你好,世界.", "你好，世界。")],
+        ),
     ];
 
     static ref AFTER_RULES: Vec<Rule> = vec![
         // Rule: halfwidth-word
-        Rule::new("halfwidth-word", halfwidth::format_word),
+        Rule::new("halfwidth-word", halfwidth::format_word).with_description(
+            "Convert the fullwidth alphanumeric characters to halfwidth.",
+            &[("ＡＢＣ１２３", "ABC123")],
+        ),
         // Rule: halfwidth punctuations
-        Rule::new("halfwidth-punctuation", halfwidth::format_punctuation),
+        Rule::new("halfwidth-punctuation", halfwidth::format_punctuation).with_description(
+            "Convert the fullwidth punctuations to halfwidth in English.",
+            &[("Come and，Join us！", "Come and, Join us!")],
+        ),
         // Rule: no-space-fullwidth
-        Rule::new("no-space-fullwidth", word::format_no_space_fullwidth),
+        Rule::new("no-space-fullwidth", word::format_no_space_fullwidth).with_description(
+            "Remove the space near the fullwidth punctuations.",
+            &[("你好 ， 世界", "你好，世界")],
+        ),
         // Rule: no-space-fullwidth-quote
-        Rule::new("no-space-fullwidth-quote", word::format_no_space_fullwidth_quote),
-        Rule::new("spellcheck", spellcheck::format),
+        Rule::new("no-space-fullwidth-quote", word::format_no_space_fullwidth_quote).with_description(
+            "Remove the space around the fullwidth quotes “”, ‘’.",
+            &[("他说 “你好” 就走了", "他说“你好”就走了")],
+        ),
+        // Rule: script-consistency
+        Rule::new("script-consistency", script::format)
+            .with_description(
+                "Keep Simplified / Traditional Chinese consistency, follow the `locale` or the majority script.",
+                &[("这是简体中文的字體", "这是简体中文的字体")],
+            ),
+        // Rule: terminology
+        Rule::new("terminology", terminology::format)
+            .with_messages(terminology::messages)
+            .with_description(
+                "Use the regional terms by the `locale`, e.g.: `信息` -> `資訊` in zh-TW.",
+                &[("查看系統信息", "查看系統資訊")],
+            )
+            .with_example_config("locale: zh-TW\nterminology:\n  packs:\n    - it\n"),
+        // Rule: spellcheck
+        Rule::new("spellcheck", spellcheck::format)
+            .with_severity(spellcheck::format_with_severity)
+            .with_description(
+                "Correct the words by the `spellcheck` config.",
+                &[("开发 ios 应用", "开发 iOS 应用")],
+            )
+            .with_example_config("spellcheck:\n  words:\n    - iOS\n"),
+    ];
+
+    /// The rules only apply in the special context.
+    static ref CONTEXT_RULES: Vec<(Context, Rule)> = vec![
+        // Rule: heading-space
+        (
+            Context::Heading,
+            Rule::new("heading-space", heading::format_space).with_description(
+                "Add a space after the heading marker.",
+                &[("##标题", "## 标题")],
+            ),
+        ),
+        // Rule: heading-punctuation
+        (
+            Context::Heading,
+            Rule::new("heading-punctuation", heading::format_punctuation).with_description(
+                "Remove the trailing fullwidth punctuations in heading.",
+                &[("## 总结：", "## 总结")],
+            ),
+        ),
     ];
 }
 
+/// The fullwidth punctuations that the spaces near them are removed by `no-space-fullwidth`.
+const FULLWIDTH_PUNCTUATIONS: &str = "，。、！？：；（）「」《》【】";
+
 lazy_static! {
     static ref FULL_DATE_RE: Regex = regexp!(
         r"[ ]{0,}\d+[ ]{0,}年 [ ]{0,}\d+[ ]{0,}月 [ ]{0,}\d+[ ]{0,}[日号][ ]{0,}"
@@ -71,14 +171,25 @@ lazy_static! {
 #[allow(dead_code)]
 pub fn default_rule_names() -> Vec<String> {
     let mut rule_names = vec![];
+    BEFORE_RULES
+        .iter()
+        .for_each(|r| rule_names.push(r.name.clone()));
     RULES.iter().for_each(|r| rule_names.push(r.name.clone()));
     AFTER_RULES
         .iter()
         .for_each(|r| rule_names.push(r.name.clone()));
+    CONTEXT_RULES
+        .iter()
+        .for_each(|(_, r)| rule_names.push(r.name.clone()));
 
     rule_names
 }
 
+/// Count the CJK chars in the text, for the stats of lint.
+pub fn cjk_count(text: &str) -> usize {
+    CJK_RE.find_iter(text).count()
+}
+
 pub(crate) fn format_or_lint(text: &str, lint: bool) -> RuleResult<'_> {
     format_or_lint_with_disable_rules(text, lint, &map![])
 }
@@ -88,48 +199,191 @@ pub(crate) fn format_or_lint_with_disable_rules<'a>(
     lint: bool,
     disable_rules: &HashMap<String, bool>,
 ) -> RuleResult<'a> {
-    let mut result = RuleResult::default();
+    format_or_lint_with_context(text, lint, disable_rules, Context::Prose)
+}
 
-    // skip if not has CJK
-    if CJK_RE.is_match(text) {
-        let mut part = String::new();
-        for ch in text.chars() {
-            part.push(ch);
-
-            // Is next char is newline or space, break part to format
-            if matches!(ch, ' ' | '\n' | '\r') {
-                let mut sub_result = RuleResult::new(&part);
-                sub_result.severity = result.severity;
-                format_part(&mut sub_result, lint, disable_rules);
-
-                let mut out = result.out.into_owned();
-                out.push_str(&sub_result.out);
-                result.out = Cow::Owned(out);
-                result.severity = sub_result.severity;
-                part.clear();
+/// Format or lint with the context of the text.
+///
+/// The rules use the severity of the `context` config, and the context-aware rules will be applied after the other rules.
+pub(crate) fn format_or_lint_with_context<'a>(
+    text: &'a str,
+    lint: bool,
+    disable_rules: &HashMap<String, bool>,
+    context: Context,
+) -> RuleResult<'a> {
+    format_or_lint_text(text, lint, disable_rules, context)
+}
+
+/// Format or lint with the context of the text, and keep the placeholders of the i18n strings.
+///
+/// The placeholders (e.g.: `%s`, `{name}`, `<b>`) are masked before the rules, and restored after.
+/// If the placeholders would be changed anyway, the text is kept, and it is an error in lint.
+pub(crate) fn format_or_lint_with_placeholders<'a>(
+    text: &'a str,
+    lint: bool,
+    disable_rules: &HashMap<String, bool>,
+    context: Context,
+) -> RuleResult<'a> {
+    let mut result = match placeholder::Masked::new(text) {
+        Some(masked) => {
+            let masked_result = format_or_lint_text(&masked.text, lint, disable_rules, context);
+            let out = masked.restore(&masked_result.out);
+            RuleResult {
+                out: if out == text {
+                    Cow::Borrowed(text)
+                } else {
+                    Cow::Owned(out)
+                },
+                severity: masked_result.severity,
+                messages: masked_result.messages,
+                rules: masked_result.rules,
+                context,
             }
         }
+        None => format_or_lint_text(text, lint, disable_rules, context),
+    };
 
-        if !part.is_empty() {
-            let mut sub_result = RuleResult::new(&part);
-            sub_result.severity = result.severity;
+    if result.out != text
+        && let Some(placeholder) = placeholder::changed(text, &result.out)
+    {
+        let message =
+            format!("The placeholder `{placeholder}` would be changed, the text is kept.");
+        result.out = Cow::Borrowed(text);
+        result.severity = Severity::Error;
+        result.messages = vec![message];
+    }
 
-            format_part(&mut sub_result, lint, disable_rules);
+    result
+}
 
-            let mut out = result.out.into_owned();
-            out.push_str(&sub_result.out);
-            result.out = Cow::Owned(out);
-            result.severity = sub_result.severity;
+fn format_or_lint_text<'a>(
+    text: &'a str,
+    lint: bool,
+    disable_rules: &HashMap<String, bool>,
+    context: Context,
+) -> RuleResult<'a> {
+    let mut result = RuleResult {
+        context,
+        ..Default::default()
+    };
+
+    // skip if the context is disabled
+    if crate::Config::current().is_disabled_text_context(context) {
+        result.out = Cow::Borrowed(text);
+        return result;
+    }
+
+    // skip if not has CJK
+    if CJK_RE.is_match(text) {
+        result.out = Cow::Borrowed(text);
+        apply_rules(BEFORE_RULES.iter(), &mut result, lint, disable_rules);
+        let text = std::mem::take(&mut result.out);
+
+        let pipeline = crate::Config::current().pipeline.unwrap_or_default();
+        match pipeline {
+            Pipeline::Chunk => format_chunks(&mut result, &text, lint, disable_rules),
+            Pipeline::Sentence => format_sentence(&mut result, &text, lint, disable_rules),
         }
     } else {
         result.out = Cow::Borrowed(text);
     }
 
     format_after_rules(&mut result, lint, disable_rules);
+    format_context_rules(&mut result, lint, disable_rules);
 
     result
 }
 
+/// Format the chunks split by space (include U+3000, NBSP) or newline, the rules only see a chunk at a time.
+fn format_chunks(
+    result: &mut RuleResult,
+    text: &str,
+    lint: bool,
+    disable_rules: &HashMap<String, bool>,
+) {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut part = String::new();
+    for (i, ch) in chars.iter().enumerate() {
+        part.push(*ch);
+
+        // Is next char is newline or space, break part to format
+        let is_break = match ch {
+            ' ' => !is_near_fullwidth_punctuation(&chars, i),
+            '\u{3000}' | '\u{A0}' | '\n' | '\r' => true,
+            _ => false,
+        };
+        if is_break {
+            append_part(result, &part, lint, disable_rules);
+            part.clear();
+        }
+    }
+
+    if !part.is_empty() {
+        append_part(result, &part, lint, disable_rules);
+    }
+}
+
+/// The spaces near the fullwidth punctuations are removed by `no-space-fullwidth`,
+/// keep them in a chunk, so the chunks are the same when the text is formatted again.
+fn is_near_fullwidth_punctuation(chars: &[char], i: usize) -> bool {
+    let is_fullwidth = |ch: &char| FULLWIDTH_PUNCTUATIONS.contains(*ch);
+    chars[..i]
+        .iter()
+        .rev()
+        .find(|ch| **ch != ' ')
+        .is_some_and(is_fullwidth)
+        || chars[i + 1..]
+            .iter()
+            .find(|ch| **ch != ' ')
+            .is_some_and(is_fullwidth)
+}
+
+/// Format the spans of the sentence, the rules can see across the words in a span.
+///
+/// The spans without CJK tokens are kept as they are, e.g.: the words between the URLs,
+/// same as the lines without CJK.
+fn format_sentence(
+    result: &mut RuleResult,
+    text: &str,
+    lint: bool,
+    disable_rules: &HashMap<String, bool>,
+) {
+    for span in sentence::spans(text) {
+        if span.protected || !span.has_cjk() {
+            let mut out = std::mem::take(&mut result.out).into_owned();
+            out.push_str(&text[span.start..span.end]);
+            result.out = Cow::Owned(out);
+            continue;
+        }
+
+        append_part(result, span.text, lint, disable_rules);
+    }
+}
+
+/// Format a part and append it to the result.
+fn append_part(
+    result: &mut RuleResult,
+    part: &str,
+    lint: bool,
+    disable_rules: &HashMap<String, bool>,
+) {
+    let mut sub_result = RuleResult::new(part);
+    sub_result.severity = result.severity;
+    sub_result.context = result.context;
+    format_part(&mut sub_result, lint, disable_rules);
+
+    let mut out = std::mem::take(&mut result.out).into_owned();
+    out.push_str(&sub_result.out);
+    result.out = Cow::Owned(out);
+    result.severity = sub_result.severity;
+    result.messages.append(&mut sub_result.messages);
+    for name in sub_result.rules {
+        if !result.rules.contains(&name) {
+            result.rules.push(name);
+        }
+    }
+}
+
 fn format_part(result: &mut RuleResult, lint: bool, disable_rules: &HashMap<String, bool>) {
     if is_match_path(&result.out) || is_match_path_hash(&result.out) {
         return;
@@ -137,16 +391,8 @@ fn format_part(result: &mut RuleResult, lint: bool, disable_rules: &HashMap<Stri
 
     let raw = result.out.clone();
 
-    for rule in RULES
-        .iter()
-        .filter(|r| !disable_rules.get(r.name.as_str()).unwrap_or(&false))
-    {
-        if lint {
-            rule.lint(result);
-        } else {
-            rule.format(result);
-        }
-    }
+    apply_rules(RULES.iter(), result, lint, disable_rules);
+    apply_custom_rules(Phase::Part, result, lint, disable_rules);
 
     // Check textRules to change result
     for (text, mode) in crate::Config::current().text_rules.iter() {
@@ -173,18 +419,50 @@ fn format_part(result: &mut RuleResult, lint: bool, disable_rules: &HashMap<Stri
 }
 
 fn format_after_rules(result: &mut RuleResult, lint: bool, disable_rules: &HashMap<String, bool>) {
-    for rule in AFTER_RULES
+    apply_rules(AFTER_RULES.iter(), result, lint, disable_rules);
+    apply_custom_rules(Phase::After, result, lint, disable_rules);
+}
+
+fn format_context_rules(
+    result: &mut RuleResult,
+    lint: bool,
+    disable_rules: &HashMap<String, bool>,
+) {
+    let context = result.context;
+    let rules = CONTEXT_RULES
         .iter()
-        .filter(|r| !disable_rules.get(r.name.as_str()).unwrap_or(&false))
-    {
-        if lint {
-            rule.lint(result);
-        } else {
-            rule.format(result);
-        }
+        .filter(|(c, _)| *c == context)
+        .map(|(_, r)| r);
+    apply_rules(rules, result, lint, disable_rules);
+}
+
+/// Apply the rules, skip the rules disabled by the toggle, e.g.: `autocorrect-disable space-word`
+fn apply_rules<'r, R: RuleTrait + ?Sized + 'r>(
+    rules: impl Iterator<Item = &'r R>,
+    result: &mut RuleResult,
+    lint: bool,
+    disable_rules: &HashMap<String, bool>,
+) {
+    for rule in rules.filter(|r| !disable_rules.get(r.name()).unwrap_or(&false)) {
+        rule::apply(rule, result, lint);
     }
 }
 
+fn apply_custom_rules(
+    phase: Phase,
+    result: &mut RuleResult,
+    lint: bool,
+    disable_rules: &HashMap<String, bool>,
+) {
+    let rules = registry::custom_rules(phase);
+    apply_rules(
+        rules.iter().map(|r| r.as_ref()),
+        result,
+        lint,
+        disable_rules,
+    );
+}
+
 fn is_match_path_hash(text: &str) -> bool {
     PATH_HASH_RE.is_match(text.trim())
 }
@@ -199,10 +477,17 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_cjk_count() {
+        assert_eq!(0, cjk_count("Hello world"));
+        assert_eq!(10, cjk_count("你好 Hello こんにちは，안녕하"));
+    }
+
     #[test]
     fn test_default_rule_names() {
         let rule_names = default_rule_names();
         let expect = vec![
+            "normalize-whitespace",
             "space-word",
             "space-punctuation",
             "space-bracket",
@@ -214,7 +499,11 @@ mod tests {
             "halfwidth-punctuation",
             "no-space-fullwidth",
             "no-space-fullwidth-quote",
+            "script-consistency",
+            "terminology",
             "spellcheck",
+            "heading-space",
+            "heading-punctuation",
         ];
         assert_eq!(expect, rule_names);
     }
@@ -274,6 +563,40 @@ mod tests {
         assert_eq!(Severity::Pass, result.severity);
     }
 
+    #[test]
+    fn test_format_sentence() {
+        crate::config::setup_test();
+
+        let format_with = |text: &str, sentence: bool| {
+            let mut result = RuleResult::default();
+            if sentence {
+                format_sentence(&mut result, text, false, &map![]);
+            } else {
+                format_chunks(&mut result, text, false, &map![]);
+            }
+            result.out.into_owned()
+        };
+
+        let cases = map! {
+            "ABC 中文 , 中文" => ("ABC 中文 , 中文", "ABC 中文，中文"),
+            "打开 https://example.com/a,b 查看 , 结果" => (
+                "打开 https://example.com/a,b 查看 , 结果",
+                "打开 https://example.com/a,b 查看，结果"
+            ),
+            "你好 hello world ! 再见" => ("你好 hello world ! 再见", "你好 hello world ! 再见"),
+            "测试ios应用， 与技术" => ("测试 ios 应用， 与技术", "测试 ios 应用， 与技术"),
+            "见 http://a.com ＡＢＣ http://b.com 中文a" => (
+                "见 http://a.com ＡＢＣ http://b.com 中文 a",
+                "见 http://a.com ＡＢＣ http://b.com 中文 a"
+            ),
+        };
+
+        for (source, (chunk, sentence)) in cases {
+            assert_eq!(chunk, format_with(source, false), "chunk: {}", source);
+            assert_eq!(sentence, format_with(source, true), "sentence: {}", source);
+        }
+    }
+
     #[test]
     fn test_rules() {
         crate::config::setup_test();
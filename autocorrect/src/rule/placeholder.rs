@@ -0,0 +1,234 @@
+// autocorrect: false
+use regex::Regex;
+
+/// The private use area chars to mask the placeholders, the rules treat them as symbols.
+const MASK_START: u32 = 0xE000;
+const MASK_END: u32 = 0xF8FF;
+
+lazy_static! {
+    /// Match the placeholder at the start, except the braces, e.g.:
+    ///
+    /// - printf: `%s`, `%d`, `%1$@`, `%lld`, `%.2f`
+    /// - markup: `<b>`, `</b>`, `<br/>`, `<a href="/">`
+    static ref PLACEHOLDER_RE: Regex = regexp!(
+        "{}",
+        r#"^(%(\d+\$)?[-+0#]*(\d+|\*)?(\.(\d+|\*))?(hh|h|ll|l|q|z|t|j|L)?[sdiufFxXoeEgGcp@]|</?[a-zA-Z][a-zA-Z\d\-]*(\s[^<>]*)?/?>)"#
+    );
+    /// Match the braces placeholder, e.g.: `{}`, `{name}`, `{0}`, `{{ count }}`,
+    /// and ICU message `{n, plural, one {# item} other {# items}}`.
+    static ref BRACES_RE: Regex = regexp!(
+        "{}",
+        r"^\{(\s*[a-zA-Z\d_][a-zA-Z\d_.]*\s*|\{\s*[a-zA-Z_][a-zA-Z\d_.]*\s*\}|\s*[a-zA-Z\d_]+\s*,\s*(plural|select|selectordinal|number|date|time)\b.*)?\}$"
+    );
+}
+
+/// Find the placeholders in the text, returns the byte ranges.
+///
+/// The braces placeholders are matched with the nested braces, e.g.: `{name}`, `{{count}}`, `%{user}`, `${name}`,
+/// and ICU message.
+pub(crate) fn find(text: &str) -> Vec<(usize, usize)> {
+    let mut ranges = vec![];
+    if !text.contains(['%', '{', '<']) {
+        return ranges;
+    }
+
+    let mut i = 0;
+    while i < text.len() {
+        let rest = &text[i..];
+        let len = braces_len(rest).or_else(|| PLACEHOLDER_RE.find(rest).map(|m| m.end()));
+
+        match len {
+            Some(len) => {
+                ranges.push((i, i + len));
+                i += len;
+            }
+            None => i += rest.chars().next().map_or(1, |c| c.len_utf8()),
+        }
+    }
+
+    ranges
+}
+
+/// The length of the braces placeholder at the start, the braces must match `BRACES_RE`.
+fn braces_len(text: &str) -> Option<usize> {
+    let start = if text.starts_with(['%', '$', '#']) {
+        1
+    } else {
+        0
+    };
+    if !text[start..].starts_with('{') {
+        return None;
+    }
+
+    let mut depth = 0;
+    for (i, c) in text.char_indices().skip(start) {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return BRACES_RE.is_match(&text[start..=i]).then_some(i + 1);
+                }
+            }
+            '\n' => return None,
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// The text with the placeholders replaced by the mask chars, and the placeholders to restore.
+pub(crate) struct Masked<'a> {
+    pub text: String,
+    placeholders: Vec<&'a str>,
+}
+
+impl<'a> Masked<'a> {
+    /// Mask the placeholders, returns `None` if no placeholders or the text already has the mask chars.
+    pub fn new(text: &'a str) -> Option<Self> {
+        let ranges = find(text);
+        if ranges.is_empty() || ranges.len() > (MASK_END - MASK_START) as usize {
+            return None;
+        }
+        if text.chars().any(is_mask) {
+            return None;
+        }
+
+        let mut masked = String::with_capacity(text.len());
+        let mut placeholders = vec![];
+        let mut last = 0;
+        for (start, end) in ranges {
+            masked.push_str(&text[last..start]);
+            masked.push(mask_char(placeholders.len()));
+            placeholders.push(&text[start..end]);
+            last = end;
+        }
+        masked.push_str(&text[last..]);
+
+        Some(Self {
+            text: masked,
+            placeholders,
+        })
+    }
+
+    /// Restore the placeholders in the formatted text.
+    pub fn restore(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        for c in text.chars() {
+            match self.placeholders.get(mask_index(c)) {
+                Some(placeholder) if is_mask(c) => out.push_str(placeholder),
+                _ => out.push(c),
+            }
+        }
+
+        out
+    }
+}
+
+fn mask_char(index: usize) -> char {
+    char::from_u32(MASK_START + index as u32).unwrap_or_default()
+}
+
+fn mask_index(c: char) -> usize {
+    (c as u32).wrapping_sub(MASK_START) as usize
+}
+
+fn is_mask(c: char) -> bool {
+    (MASK_START..=MASK_END).contains(&(c as u32))
+}
+
+/// Find the first placeholder changed between the texts, by compare them as the multisets.
+pub(crate) fn changed<'a>(old: &'a str, new: &'a str) -> Option<&'a str> {
+    let mut old_placeholders = find(old)
+        .into_iter()
+        .map(|(start, end)| &old[start..end])
+        .collect::<Vec<_>>();
+    let mut new_placeholders = find(new)
+        .into_iter()
+        .map(|(start, end)| &new[start..end])
+        .collect::<Vec<_>>();
+    old_placeholders.sort_unstable();
+    new_placeholders.sort_unstable();
+
+    if old_placeholders == new_placeholders {
+        return None;
+    }
+
+    old_placeholders
+        .iter()
+        .find(|p| !new_placeholders.contains(p))
+        .or_else(|| {
+            new_placeholders
+                .iter()
+                .find(|p| !old_placeholders.contains(p))
+        })
+        .or(old_placeholders.first())
+        .or(new_placeholders.first())
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn assert_find(expected: Vec<&str>, text: &str) {
+        let actual = find(text)
+            .into_iter()
+            .map(|(start, end)| &text[start..end])
+            .collect::<Vec<_>>();
+        assert_eq!(expected, actual, "{text}");
+    }
+
+    #[test]
+    fn test_find() {
+        assert_find(vec!["%s", "%d"], "你好%s，共%d个");
+        assert_find(vec!["%1$@", "%2$lld", "%.2f"], "%1$@和%2$lld的%.2f");
+        assert_find(
+            vec!["{name}", "{{count}}", "{{ count }}"],
+            "{name}{{count}}和{{ count }}",
+        );
+        assert_find(
+            vec!["%{user}", "${name}", "#{name}", "{}"],
+            "%{user}${name}#{name}{}",
+        );
+        assert_find(vec!["<b>", "</b>", "<br/>"], "点击<b>这里</b><br/>");
+        assert_find(vec![r#"<a href="/">"#, "</a>"], r#"<a href="/">链接</a>"#);
+        assert_find(
+            vec!["{n, plural, one {# item} other {# items}}"],
+            "共{n, plural, one {# item} other {# items}}",
+        );
+
+        assert_find(vec![], "100%的用户，50% off");
+        assert_find(vec![], "a < b，{中文}，{Hello 世界}，{ a && t(\"中文\") }");
+        assert_find(vec![], "{unclosed");
+    }
+
+    #[test]
+    fn test_masked() {
+        let text = "你好%s，共{{count}}个<b>文件</b>";
+        let masked = Masked::new(text).unwrap();
+        assert_eq!(
+            "你好\u{E000}，共\u{E001}个\u{E002}文件\u{E003}",
+            masked.text
+        );
+        assert_eq!(text, masked.restore(&masked.text));
+        assert_eq!(
+            "你好%s，共 {{count}} 个<b>文件</b>",
+            masked.restore("你好\u{E000}，共 \u{E001} 个\u{E002}文件\u{E003}")
+        );
+
+        assert!(Masked::new("你好世界").is_none());
+        assert!(Masked::new("你好%s\u{E000}").is_none());
+    }
+
+    #[test]
+    fn test_changed() {
+        assert_eq!(None, changed("你好%s，共%d个", "你好 %s，共 %d 个"));
+        assert_eq!(None, changed("%s和%s", "%s 和 %s"));
+        assert_eq!(Some("{count}"), changed("共{count}个", "共{ count}个"));
+        assert_eq!(Some("%s"), changed("%s和%s", "%s 和"));
+        assert_eq!(Some("{count}"), changed("共｛count｝个", "共{count}个"));
+    }
+}
@@ -0,0 +1,236 @@
+use std::{
+    borrow::Cow,
+    sync::{Arc, RwLock},
+};
+
+use serde::Serialize;
+
+use crate::config::SeverityMode;
+
+use super::Context;
+
+lazy_static! {
+    static ref CUSTOM_RULES: RwLock<Vec<Arc<dyn Rule>>> = RwLock::new(vec![]);
+}
+
+/// The phase to apply a rule.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    /// Apply to each part of a line, the part is split by the `pipeline` config, like the `space-word` rule.
+    ///
+    /// Like the built-in rules of this phase, it is only applied to the line has CJK, use `After` for the other lines.
+    Part,
+    /// Apply to the whole line after the built-in rules, like the `spellcheck` rule.
+    #[default]
+    After,
+}
+
+impl std::str::FromStr for Phase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "part" => Ok(Phase::Part),
+            "after" => Ok(Phase::After),
+            _ => Err(format!("Invalid rule phase `{s}`, available: part, after")),
+        }
+    }
+}
+
+/// A rule to format or lint the text.
+///
+/// The rule takes part in the toggles (e.g.: `autocorrect-disable <name>`),
+/// the severity in `rules` and `context` config, and the lint results, by the `name`.
+///
+/// ```
+/// use std::borrow::Cow;
+/// use autocorrect::{Context, Engine, Rule};
+///
+/// struct NoEllipsis;
+///
+/// impl Rule for NoEllipsis {
+///     fn name(&self) -> &str {
+///         "no-ellipsis"
+///     }
+///
+///     fn format<'a>(&self, input: &'a str, _context: &Context) -> Cow<'a, str> {
+///         if input.contains("...") {
+///             Cow::Owned(input.replace("...", "……"))
+///         } else {
+///             Cow::Borrowed(input)
+///         }
+///     }
+/// }
+///
+/// Engine::register_rule(NoEllipsis);
+/// assert_eq!("你好……", autocorrect::format("你好..."));
+/// Engine::unregister_rule("no-ellipsis");
+/// ```
+pub trait Rule: Send + Sync {
+    /// The name of the rule, used in the config and the toggles, e.g.: `no-ellipsis`
+    fn name(&self) -> &str;
+
+    /// The phase to apply the rule, default is `After`, the `Part` rules are only applied to the line has CJK.
+    fn phase(&self) -> Phase {
+        Phase::After
+    }
+
+    /// The severity when the rule is not in the `rules` config.
+    fn default_severity(&self) -> SeverityMode {
+        SeverityMode::Error
+    }
+
+    /// Describe what the rule does.
+    fn description(&self) -> &str {
+        ""
+    }
+
+    /// The examples of bad and good text, e.g.: `("你好...", "你好……")`
+    fn examples(&self) -> &[(&str, &str)] {
+        &[]
+    }
+
+    /// Format the input, return `Cow::Borrowed` if nothing changed.
+    fn format<'a>(&self, input: &'a str, context: &Context) -> Cow<'a, str>;
+
+    /// Lint messages for the changes, e.g.: "Prefer `資訊` over `信息`"
+    fn messages(&self, _input: &str, _context: &Context) -> Vec<String> {
+        vec![]
+    }
+
+    /// Format with the rule severity, returns the new text and the severity of the changes.
+    ///
+    /// Override it when the rule has its own severity for some matches, only `error` changes will apply when format.
+    fn format_with_severity<'a>(
+        &self,
+        input: &'a str,
+        context: &Context,
+        severity: SeverityMode,
+        lint: bool,
+    ) -> (Cow<'a, str>, SeverityMode) {
+        if !lint && severity != SeverityMode::Error {
+            return (Cow::Borrowed(input), severity);
+        }
+
+        (self.format(input, context), severity)
+    }
+}
+
+/// The registry of the custom rules.
+pub struct Engine;
+
+impl Engine {
+    /// Register a custom rule, the rule with the same name will be replaced.
+    pub fn register_rule<R: Rule + 'static>(rule: R) {
+        let mut rules = CUSTOM_RULES.write().unwrap();
+        rules.retain(|r| r.name() != rule.name());
+        rules.push(Arc::new(rule));
+    }
+
+    /// Unregister a custom rule by name, returns `true` if the rule was registered.
+    pub fn unregister_rule(name: &str) -> bool {
+        let mut rules = CUSTOM_RULES.write().unwrap();
+        let len = rules.len();
+        rules.retain(|r| r.name() != name);
+        rules.len() != len
+    }
+
+    /// The names of the registered custom rules.
+    pub fn rule_names() -> Vec<String> {
+        CUSTOM_RULES
+            .read()
+            .unwrap()
+            .iter()
+            .map(|r| r.name().to_owned())
+            .collect()
+    }
+}
+
+/// The custom rules in the phase.
+pub(crate) fn custom_rules(phase: Phase) -> Vec<Arc<dyn Rule>> {
+    CUSTOM_RULES
+        .read()
+        .unwrap()
+        .iter()
+        .filter(|r| r.phase() == phase)
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::result::Severity;
+    use std::collections::HashMap;
+
+    struct ReplaceRule;
+
+    impl Rule for ReplaceRule {
+        fn name(&self) -> &str {
+            "test-registry"
+        }
+
+        fn phase(&self) -> Phase {
+            Phase::Part
+        }
+
+        fn default_severity(&self) -> SeverityMode {
+            SeverityMode::Warning
+        }
+
+        fn format<'a>(&self, input: &'a str, context: &Context) -> Cow<'a, str> {
+            if *context == Context::Prose && input.contains("旧术语") {
+                Cow::Owned(input.replace("旧术语", "新术语"))
+            } else {
+                Cow::Borrowed(input)
+            }
+        }
+
+        fn messages(&self, _input: &str, _context: &Context) -> Vec<String> {
+            vec!["Prefer `新术语` over `旧术语`".to_owned()]
+        }
+    }
+
+    #[test]
+    fn test_register_rule() {
+        crate::config::setup_test();
+
+        Engine::register_rule(ReplaceRule);
+        Engine::register_rule(ReplaceRule);
+        assert_eq!(
+            1,
+            Engine::rule_names()
+                .iter()
+                .filter(|name| *name == "test-registry")
+                .count()
+        );
+
+        // Warning severity only changes the text in lint
+        let result = crate::rule::format_or_lint("使用旧术语", false);
+        assert_eq!("使用旧术语", result.out);
+
+        let result = crate::rule::format_or_lint("使用旧术语", true);
+        assert_eq!("使用新术语", result.out);
+        assert_eq!(Severity::Warning, result.severity);
+        assert_eq!(vec!["Prefer `新术语` over `旧术语`"], result.messages);
+
+        // Disabled by the toggle
+        let result = crate::rule::format_or_lint_with_disable_rules(
+            "使用旧术语",
+            true,
+            &map! { "test-registry".to_owned() => true },
+        );
+        assert_eq!(Severity::Pass, result.severity);
+
+        // Not in the context
+        let result =
+            crate::rule::format_or_lint_with_context("使用旧术语", true, &map![], Context::Comment);
+        assert_eq!(Severity::Pass, result.severity);
+
+        assert!(Engine::unregister_rule("test-registry"));
+        assert!(!Engine::unregister_rule("test-registry"));
+        let result = crate::rule::format_or_lint("使用旧术语", true);
+        assert_eq!(Severity::Pass, result.severity);
+    }
+}
@@ -3,16 +3,38 @@ use std::borrow::Cow;
 use crate::config::SeverityMode;
 use crate::result::Severity;
 
+use super::{registry, Context};
+
 pub(crate) struct Rule {
     #[allow(dead_code)]
     pub name: String,
     pub format_fn: for<'a> fn(input: &'a str) -> Cow<'a, str>,
+    /// Optional, to describe the lint result, e.g.: "Prefer `資訊` over `信息`"
+    pub messages_fn: Option<fn(input: &str) -> Vec<String>>,
+    /// Optional, for the rule has its own severity for some matches, e.g.: spellcheck words with `severity`.
+    ///
+    /// It takes the rule severity and is lint, returns the new text and the severity of the changes.
+    pub severity_format_fn: Option<SeverityFormatFn>,
+    /// Describe what the rule does, for `autocorrect rules` and `autocorrect explain`.
+    pub description: &'static str,
+    /// The examples of bad and good text.
+    pub examples: &'static [(&'static str, &'static str)],
+    /// Optional, the config to run the examples, e.g.: `locale` for `terminology`.
+    pub example_config: Option<&'static str>,
 }
 
+pub(crate) type SeverityFormatFn =
+    for<'a> fn(input: &'a str, severity: SeverityMode, lint: bool) -> (Cow<'a, str>, SeverityMode);
+
 #[derive(Default)]
 pub(crate) struct RuleResult<'a> {
     pub out: Cow<'a, str>,
     pub severity: Severity,
+    pub messages: Vec<String>,
+    /// The names of the rules that changed the text, only for lint.
+    pub rules: Vec<String>,
+    /// The context of the text, for the rule severity in the context.
+    pub context: Context,
 }
 
 impl<'a> RuleResult<'a> {
@@ -29,48 +51,126 @@ impl Rule {
         Rule {
             name: name.to_string(),
             format_fn: format,
+            messages_fn: None,
+            severity_format_fn: None,
+            description: "",
+            examples: &[],
+            example_config: None,
         }
     }
 
-    pub fn format(&self, result: &mut RuleResult) {
-        if self.severity() != SeverityMode::Error {
-            return;
-        }
+    /// Set the description and the examples of bad and good text
+    pub fn with_description(
+        mut self,
+        description: &'static str,
+        examples: &'static [(&'static str, &'static str)],
+    ) -> Self {
+        self.description = description;
+        self.examples = examples;
+        self
+    }
 
-        if let Cow::Owned(new) = (self.format_fn)(&result.out) {
-            result.severity = Severity::Error;
-            result.out = Cow::Owned(new);
-        }
+    /// Set the config to run the examples
+    pub fn with_example_config(mut self, config: &'static str) -> Self {
+        self.example_config = Some(config);
+        self
+    }
+
+    /// Set the messages fn for lint
+    pub fn with_messages(mut self, messages: fn(input: &str) -> Vec<String>) -> Self {
+        self.messages_fn = Some(messages);
+        self
+    }
+
+    /// Set the format fn that decides the severity by itself
+    pub fn with_severity(mut self, format: SeverityFormatFn) -> Self {
+        self.severity_format_fn = Some(format);
+        self
+    }
+}
+
+impl registry::Rule for Rule {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The built-in rules are off if not in the `rules` config.
+    fn default_severity(&self) -> SeverityMode {
+        SeverityMode::Off
     }
 
-    pub fn lint(&self, result: &mut RuleResult) {
-        if self.severity() == SeverityMode::Off {
-            return;
+    fn description(&self) -> &str {
+        self.description
+    }
+
+    fn examples(&self) -> &[(&str, &str)] {
+        self.examples
+    }
+
+    fn format<'a>(&self, input: &'a str, _context: &Context) -> Cow<'a, str> {
+        (self.format_fn)(input)
+    }
+
+    fn messages(&self, input: &str, _context: &Context) -> Vec<String> {
+        self.messages_fn.map(|f| f(input)).unwrap_or_default()
+    }
+
+    fn format_with_severity<'a>(
+        &self,
+        input: &'a str,
+        _context: &Context,
+        severity: SeverityMode,
+        lint: bool,
+    ) -> (Cow<'a, str>, SeverityMode) {
+        if let Some(severity_format_fn) = self.severity_format_fn {
+            return severity_format_fn(input, severity, lint);
         }
 
-        if let Cow::Owned(new) = (self.format_fn)(&result.out) {
-            if result.severity == Severity::Pass {
-                if self.severity() == SeverityMode::Warning {
-                    result.severity = Severity::Warning;
-                } else {
-                    result.severity = Severity::Error;
-                }
-            }
-            result.out = Cow::Owned(new);
+        if !lint && severity != SeverityMode::Error {
+            return (Cow::Borrowed(input), severity);
         }
+
+        ((self.format_fn)(input), severity)
+    }
+}
+
+/// Apply the rule to format or lint the result, by the rule severity in the context of the result.
+pub(crate) fn apply<R: registry::Rule + ?Sized>(rule: &R, result: &mut RuleResult, lint: bool) {
+    let context = result.context;
+    let severity = severity(rule, context);
+    if severity == SeverityMode::Off {
+        return;
     }
 
-    fn severity(&self) -> SeverityMode {
-        let config = crate::Config::current();
+    let (out, severity) = rule.format_with_severity(&result.out, &context, severity, lint);
 
-        if let Some(s) = config.rules.get(&self.name) {
-            *s
-        } else {
-            SeverityMode::Off
+    if let Cow::Owned(new) = out {
+        if !lint {
+            result.severity = Severity::Error;
+        } else if result.severity == Severity::Pass {
+            if severity == SeverityMode::Warning {
+                result.severity = Severity::Warning;
+            } else {
+                result.severity = Severity::Error;
+            }
         }
+
+        if lint {
+            result.messages.extend(rule.messages(&result.out, &context));
+            if !result.rules.iter().any(|name| name == rule.name()) {
+                result.rules.push(rule.name().to_owned());
+            }
+        }
+        result.out = Cow::Owned(new);
     }
 }
 
+fn severity<R: registry::Rule + ?Sized>(rule: &R, context: Context) -> SeverityMode {
+    crate::Config::current()
+        .get_rule_severity(rule.name(), context)
+        .unwrap_or_else(|| rule.default_severity())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,30 +178,55 @@ mod tests {
     #[test]
     fn test_rule_not_pass() {
         let rule = Rule::new("space-word", |input| Cow::Owned(format!("{input} - foo")));
-        assert_eq!(rule.severity(), SeverityMode::Error);
+        assert_eq!(severity(&rule, Context::Prose), SeverityMode::Error);
         assert_eq!(rule.name, "space-word");
 
         let mut result = RuleResult::new("test");
-        rule.format(&mut result);
+        apply(&rule, &mut result, false);
         assert_eq!(result.out, "test - foo");
         assert_eq!(result.severity, Severity::Error);
 
         let mut result = RuleResult::new("test");
-        rule.lint(&mut result);
+        apply(&rule, &mut result, true);
         assert_eq!(result.out, "test - foo");
         assert_eq!(result.severity, Severity::Error);
     }
 
+    #[test]
+    fn test_rule_messages() {
+        let rule = Rule::new("space-word", |input| {
+            Cow::Owned(input.replace("foo", "bar"))
+        })
+        .with_messages(|input| {
+            if input.contains("foo") {
+                vec!["Prefer `bar` over `foo`".to_owned()]
+            } else {
+                vec![]
+            }
+        });
+
+        let mut result = RuleResult::new("test foo");
+        apply(&rule, &mut result, false);
+        assert_eq!(result.out, "test bar");
+        assert!(result.messages.is_empty());
+
+        let mut result = RuleResult::new("test foo");
+        apply(&rule, &mut result, true);
+        assert_eq!(result.out, "test bar");
+        assert_eq!(result.messages, vec!["Prefer `bar` over `foo`"]);
+        assert_eq!(result.rules, vec!["space-word"]);
+    }
+
     #[test]
     fn test_rule_pass() {
         let rule = Rule::new("spellcheck", |input| Cow::Borrowed(input));
 
         let mut result = RuleResult::new("test");
-        rule.format(&mut result);
+        apply(&rule, &mut result, false);
         assert_eq!(result.out, "test");
         assert_eq!(result.severity, Severity::Pass);
 
-        rule.lint(&mut result);
+        apply(&rule, &mut result, true);
         assert_eq!(result.out, "test");
         assert_eq!(result.severity, Severity::Pass);
     }
@@ -0,0 +1,299 @@
+// autocorrect: false
+use std::{
+    borrow::Cow,
+    cell::Cell,
+    collections::{HashMap, HashSet},
+};
+
+use crate::{config::Config, keyword};
+
+lazy_static! {
+    static ref CHARS_STR: &'static str = include_str!("../../dict/script/chars.txt");
+    static ref T2S_STR: &'static str = include_str!("../../dict/script/t2s.txt");
+    static ref S2T_STR: &'static str = include_str!("../../dict/script/s2t.txt");
+    static ref PHRASES_STR: &'static str = include_str!("../../dict/script/phrases.txt");
+    /// Simplified to Traditional
+    static ref S2T_DICT: ScriptDict = ScriptDict::new(
+        parse_pairs(&PHRASES_STR)
+            .chain(parse_pairs(&CHARS_STR))
+            .chain(parse_pairs(&S2T_STR))
+    );
+    /// Traditional to Simplified
+    static ref T2S_DICT: ScriptDict = ScriptDict::new(
+        parse_pairs(&PHRASES_STR)
+            .chain(parse_pairs(&CHARS_STR))
+            .map(|(s, t)| (t, s))
+            .chain(parse_pairs(&T2S_STR))
+    );
+    /// The chars only exist in Simplified Chinese, for detect script.
+    static ref SIMPLIFIED_CHARS: HashSet<char> = parse_pairs(&CHARS_STR)
+        .filter_map(|(s, _)| s.chars().next())
+        .collect();
+    /// The chars only exist in Traditional Chinese, for detect script.
+    static ref TRADITIONAL_CHARS: HashSet<char> = parse_pairs(&CHARS_STR)
+        .map(|(_, t)| t)
+        .chain(parse_pairs(&T2S_STR).map(|(t, _)| t))
+        .filter_map(|t| t.chars().next())
+        .collect();
+}
+
+thread_local! {
+    /// The majority script of the whole document being formatted, see `with_document_script`.
+    static DOCUMENT_SCRIPT: Cell<Option<Option<Script>>> = const { Cell::new(None) };
+}
+
+/// The Chinese script that the text should be written in.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Script {
+    Simplified,
+    Traditional,
+}
+
+impl Script {
+    /// Get script from locale, e.g.: `zh-CN`, `zh-TW`, `zh-Hant`.
+    ///
+    /// Return None if the locale is not a Chinese locale.
+    pub fn from_locale(locale: &str) -> Option<Self> {
+        let locale = locale.trim().replace('_', "-").to_lowercase();
+        match locale.as_str() {
+            "zh-tw" | "zh-hk" | "zh-mo" | "zh-hant" => Some(Script::Traditional),
+            "zh" | "zh-cn" | "zh-sg" | "zh-hans" => Some(Script::Simplified),
+            _ => {
+                if locale.starts_with("zh-hant") {
+                    Some(Script::Traditional)
+                } else if locale.starts_with("zh-hans") {
+                    Some(Script::Simplified)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Detect the script by majority of the characters that only exist in one script.
+    pub fn detect(text: &str) -> Option<Self> {
+        let mut simplified = 0;
+        let mut traditional = 0;
+        for c in text.chars() {
+            if SIMPLIFIED_CHARS.contains(&c) {
+                simplified += 1;
+            } else if TRADITIONAL_CHARS.contains(&c) {
+                traditional += 1;
+            }
+        }
+
+        match simplified.cmp(&traditional) {
+            std::cmp::Ordering::Greater => Some(Script::Simplified),
+            std::cmp::Ordering::Less => Some(Script::Traditional),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+}
+
+struct ScriptDict {
+    /// key is the phrase or char to replace, value is the new one.
+    map: HashMap<String, String>,
+    matcher: keyword::Node,
+}
+
+impl ScriptDict {
+    fn new<'a>(pairs: impl Iterator<Item = (&'a str, &'a str)>) -> Self {
+        let mut map = HashMap::new();
+        for (from, to) in pairs {
+            // The first one wins, so phrases must be added before chars.
+            map.entry(from.to_string()).or_insert(to.to_string());
+        }
+
+        let mut matcher = keyword::Node::new(false);
+        matcher.add_keywords(map.keys());
+        matcher.build();
+
+        Self { map, matcher }
+    }
+
+    /// Replace all matched phrases and chars, the longer match has the higher priority.
+    fn replace<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let matched = self.matcher.match_keywords(text);
+        if matched.is_empty() {
+            return Cow::Borrowed(text);
+        }
+
+        let mut spans = matched
+            .iter()
+            .flat_map(|(old, (len, starts))| starts.iter().map(move |start| (*start, *len, *old)))
+            .collect::<Vec<_>>();
+        spans.sort_unstable_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)));
+
+        let chars = text.chars().collect::<Vec<_>>();
+        let mut out = String::with_capacity(text.len());
+        let mut cursor = 0;
+        let mut changed = false;
+        for (start, len, old) in spans {
+            if start < cursor {
+                continue;
+            }
+
+            let Some(new) = self.map.get(old) else {
+                continue;
+            };
+
+            out.extend(&chars[cursor..start]);
+            out.push_str(new);
+            cursor = start + len;
+            // Some phrases are kept as is, e.g.: `皇后`, they are only to protect the chars.
+            if new != old {
+                changed = true;
+            }
+        }
+
+        if !changed {
+            return Cow::Borrowed(text);
+        }
+
+        out.extend(&chars[cursor..]);
+        Cow::Owned(out)
+    }
+}
+
+fn parse_pairs(data: &str) -> impl Iterator<Item = (&str, &str)> {
+    data.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once(' '))
+        .map(|(from, to)| (from.trim(), to.trim()))
+}
+
+fn has_kana(text: &str) -> bool {
+    text.chars()
+        .any(|c| matches!(c, '\u{3040}'..='\u{309F}' | '\u{30A0}'..='\u{30FF}'))
+}
+
+/// Detect the majority script once for the whole document, and use it in `format` for all its lines.
+///
+/// The nested calls (e.g.: the code blocks in Markdown) keep the script of the outermost document.
+pub(crate) fn with_document_script<T>(text: &str, f: impl FnOnce() -> T) -> T {
+    if DOCUMENT_SCRIPT.get().is_some() || Config::current().locale.is_some() {
+        return f();
+    }
+
+    DOCUMENT_SCRIPT.set(Some(Script::detect(text)));
+    let out = f();
+    DOCUMENT_SCRIPT.set(None);
+    out
+}
+
+/// Keep Simplified / Traditional Chinese consistency.
+///
+/// The script is follow the `locale` config, or detect by the majority of the whole document.
+pub fn format(text: &str) -> Cow<'_, str> {
+    // Japanese Kanji are shared with both scripts, skip it.
+    if has_kana(text) {
+        return Cow::Borrowed(text);
+    }
+
+    let locale = Config::current().locale.clone();
+    let script = match locale {
+        Some(locale) => Script::from_locale(&locale),
+        None => DOCUMENT_SCRIPT
+            .get()
+            .unwrap_or_else(|| Script::detect(text)),
+    };
+
+    match script {
+        Some(Script::Traditional) => S2T_DICT.replace(text),
+        Some(Script::Simplified) => T2S_DICT.replace(text),
+        None => Cow::Borrowed(text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_from_locale() {
+        assert_eq!(Some(Script::Traditional), Script::from_locale("zh-TW"));
+        assert_eq!(Some(Script::Traditional), Script::from_locale("zh_HK"));
+        assert_eq!(Some(Script::Traditional), Script::from_locale("zh-Hant-TW"));
+        assert_eq!(Some(Script::Simplified), Script::from_locale("zh-CN"));
+        assert_eq!(Some(Script::Simplified), Script::from_locale("zh-Hans"));
+        assert_eq!(Some(Script::Simplified), Script::from_locale("zh"));
+        assert_eq!(None, Script::from_locale("ja"));
+        assert_eq!(None, Script::from_locale("en"));
+    }
+
+    #[test]
+    fn test_script_detect() {
+        assert_eq!(Some(Script::Traditional), Script::detect("這是一個軟件"));
+        assert_eq!(Some(Script::Simplified), Script::detect("这是一个軟件"));
+        assert_eq!(None, Script::detect("中文"));
+        assert_eq!(None, Script::detect("皇后"));
+        assert_eq!(None, Script::detect("Hello world"));
+    }
+
+    #[test]
+    fn test_simplified_to_traditional() {
+        let cases = map! {
+            "軟件" => "軟件",
+            "這是一個软件" => "這是一個軟體",
+            "請下載最新版本的软件和硬件" => "請下載最新版本的軟體和硬體",
+            "头发很長" => "頭髮很長",
+            "開发文件" => "開發文件",
+            "皇后的故事" => "皇后的故事",
+            "一公里以后" => "一公里以後",
+            "之后的內容" => "之後的內容",
+            "台湾的網絡" => "台灣的網絡",
+        };
+
+        for (source, expected) in cases {
+            assert_eq!(expected, S2T_DICT.replace(source), "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_traditional_to_simplified() {
+        let cases = map! {
+            "软件" => "软件",
+            "这是一个軟體" => "这是一个软件",
+            "头發很长" => "头发很长",
+            "以後再说" => "以后再说",
+            "系统關係" => "系统关系",
+        };
+
+        for (source, expected) in cases {
+            assert_eq!(expected, T2S_DICT.replace(source), "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_format() {
+        let cases = map! {
+            "這是一個软件" => "這是一個軟體",
+            "这是一个简单的軟體" => "这是一个简单的软件",
+            "中文" => "中文",
+            // Japanese
+            "ソフトウェアの開発" => "ソフトウェアの開発",
+        };
+
+        for (source, expected) in cases {
+            assert_eq!(expected, format(source), "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_format_follows_document_script() {
+        let raw = "這是一個軟體。\n這個說明很長。\n这个软件";
+        assert_eq!("这个软件", format("这个软件"));
+
+        with_document_script(raw, || {
+            assert_eq!("這個軟體", format("这个软件"));
+            // The nested document keeps the script of the outermost document.
+            with_document_script("这个软件", || {
+                assert_eq!("這個軟體", format("这个软件"));
+            });
+            assert_eq!("這個軟體", format("这个软件"));
+        });
+
+        assert_eq!("这个软件", format("这个软件"));
+    }
+}
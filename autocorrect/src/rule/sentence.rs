@@ -0,0 +1,217 @@
+// autocorrect: false
+use super::{is_match_path, is_match_path_hash, CJK_RE};
+
+/// The script class of a token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Script {
+    /// CJK chars, e.g.: `中文`, `日本語`, `한국어`
+    Cjk,
+    /// Latin letters, e.g.: `Hello`
+    Latin,
+    /// Numbers, e.g.: `2024`
+    Number,
+    /// Spaces and newlines
+    Space,
+    /// Punctuations, e.g.: `,`, `。`
+    Punctuation,
+    /// Other chars, e.g.: emoji, symbols
+    Other,
+}
+
+impl Script {
+    fn of(ch: char) -> Self {
+        if ch.is_whitespace() {
+            Script::Space
+        } else if ch.is_ascii_digit() {
+            Script::Number
+        } else if is_cjk(ch) {
+            Script::Cjk
+        } else if ch.is_alphabetic() {
+            Script::Latin
+        } else if ch.is_ascii_punctuation() || is_fullwidth_punctuation(ch) {
+            Script::Punctuation
+        } else {
+            Script::Other
+        }
+    }
+}
+
+fn is_cjk(ch: char) -> bool {
+    let mut buf = [0; 4];
+    CJK_RE.is_match(ch.encode_utf8(&mut buf))
+}
+
+fn is_fullwidth_punctuation(ch: char) -> bool {
+    matches!(ch, '\u{3000}'..='\u{303F}' | '\u{FF01}'..='\u{FF0F}' | '\u{FF1A}'..='\u{FF20}')
+}
+
+/// A token in the sentence, the chars in a token have the same script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Token<'a> {
+    pub script: Script,
+    pub text: &'a str,
+    /// Byte offset of the start in the sentence
+    pub start: usize,
+    /// Byte offset of the end in the sentence
+    pub end: usize,
+    /// Char offset of the start in the sentence
+    pub char_start: usize,
+}
+
+/// Split the text into tokens by the script class.
+pub(crate) fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let mut tokens: Vec<Token> = vec![];
+
+    for (char_start, (start, ch)) in text.char_indices().enumerate() {
+        let script = Script::of(ch);
+        let end = start + ch.len_utf8();
+
+        match tokens.last_mut() {
+            // Punctuations are always single char tokens, e.g.: `,.` is two tokens
+            Some(last) if last.script == script && script != Script::Punctuation => {
+                last.end = end;
+                last.text = &text[last.start..end];
+            }
+            _ => tokens.push(Token {
+                script,
+                text: &text[start..end],
+                start,
+                end,
+                char_start,
+            }),
+        }
+    }
+
+    tokens
+}
+
+/// A span of the sentence, the rules are applied to the spans that not protected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Span<'a> {
+    pub text: &'a str,
+    pub tokens: Vec<Token<'a>>,
+    /// Byte offset of the start in the sentence
+    pub start: usize,
+    /// Byte offset of the end in the sentence
+    pub end: usize,
+    /// Char offset of the start in the sentence
+    pub char_start: usize,
+    /// Keep the span as it is, e.g.: path or URL
+    pub protected: bool,
+}
+
+impl Span<'_> {
+    /// The span has CJK tokens, the rules only change the text near the CJK.
+    pub fn has_cjk(&self) -> bool {
+        self.tokens.iter().any(|token| token.script == Script::Cjk)
+    }
+}
+
+/// Split the sentence into spans by the tokens.
+///
+/// The words (split by the space tokens) like path or URL are protected,
+/// and the other words are joined with the spaces between them, so the rules can see across the words.
+pub(crate) fn spans(text: &str) -> Vec<Span<'_>> {
+    let mut spans: Vec<Span> = vec![];
+
+    let mut tokens = tokenize(text).into_iter().peekable();
+    while let Some(first) = tokens.next() {
+        let is_space = first.script == Script::Space;
+
+        // Take a word or the spaces
+        let mut word = vec![first];
+        while let Some(token) = tokens.next_if(|t| (t.script == Script::Space) == is_space) {
+            word.push(token);
+        }
+
+        let (start, char_start) = (word[0].start, word[0].char_start);
+        let end = word[word.len() - 1].end;
+        let protected = !is_space
+            && (is_match_path(&text[start..end]) || is_match_path_hash(&text[start..end]));
+
+        match spans.last_mut() {
+            Some(last) if !last.protected && !protected => {
+                last.end = end;
+                last.text = &text[last.start..end];
+                last.tokens.append(&mut word);
+            }
+            _ => spans.push(Span {
+                text: &text[start..end],
+                tokens: word,
+                start,
+                end,
+                char_start,
+                protected,
+            }),
+        }
+    }
+
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        let tokens = tokenize("Hello世界 2024,好");
+        let actual = tokens
+            .iter()
+            .map(|t| (t.script, t.text, t.start, t.end, t.char_start))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![
+                (Script::Latin, "Hello", 0, 5, 0),
+                (Script::Cjk, "世界", 5, 11, 5),
+                (Script::Space, " ", 11, 12, 7),
+                (Script::Number, "2024", 12, 16, 8),
+                (Script::Punctuation, ",", 16, 17, 12),
+                (Script::Cjk, "好", 17, 20, 13),
+            ],
+            actual
+        );
+
+        let tokens = tokenize("你好，。🎉");
+        let scripts = tokens.iter().map(|t| t.script).collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                Script::Cjk,
+                Script::Punctuation,
+                Script::Punctuation,
+                Script::Other
+            ],
+            scripts
+        );
+        assert!(tokenize("").is_empty());
+    }
+
+    #[test]
+    fn test_spans() {
+        let text = "打开 https://example.com 查看 ABC 中文 , 中文";
+        let spans = spans(text)
+            .into_iter()
+            .map(|s| (s.text, s.char_start, s.protected, s.has_cjk()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("打开 ", 0, false, true),
+                ("https://example.com", 3, true, false),
+                (" 查看 ABC 中文 , 中文", 22, false, true),
+            ],
+            spans
+        );
+
+        let spans = super::spans("测试 foo.html#测试test");
+        assert_eq!(2, spans.len());
+        assert_eq!((0, 7), (spans[0].start, spans[0].end));
+        assert!(spans[1].protected);
+        assert_eq!(text.len(), super::spans(text).last().unwrap().end);
+
+        let spans = super::spans("见 http://a.com and http://b.com");
+        assert_eq!(" and ", spans[2].text);
+        assert!(!spans[2].has_cjk());
+        assert!(super::spans("").is_empty());
+    }
+}
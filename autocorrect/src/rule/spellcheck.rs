@@ -1,46 +1,55 @@
-use std::{borrow::Cow, collections::HashMap};
+use std::borrow::Cow;
 
-use crate::{config::Config, keyword::MatchedResult};
+use crate::config::{Config, SeverityMode, SpellcheckConfig, SpellcheckEntry};
 
 // Spell check by dict
 pub fn format(text: &str) -> Cow<'_, str> {
-    let config = Config::current();
+    format_with_severity(text, SeverityMode::Error, true).0
+}
 
-    let word_map = &config.spellcheck.word_map;
-    let matcher = &config.spellcheck.matcher;
+/// Spell check with the severity of each word.
+///
+/// The words with `Warning` severity are only be replaced in lint.
+pub fn format_with_severity(
+    text: &str,
+    severity: SeverityMode,
+    lint: bool,
+) -> (Cow<'_, str>, SeverityMode) {
+    let config = Config::current();
 
-    let matched_words = matcher.match_keywords(text);
-    if matched_words.is_empty() {
-        return Cow::Borrowed(text);
+    let spans = match_spans(text, &config.spellcheck);
+    if spans.is_empty() {
+        return (Cow::Borrowed(text), severity);
     }
-    replace_with_spans(text, matched_words, word_map)
+
+    replace_with_spans(text, spans, severity, lint)
 }
 
 #[derive(Debug)]
 struct SpanInfo<'a> {
-    new: &'a String,
+    new: Cow<'a, str>,
+    entry: &'a SpellcheckEntry,
     old_chars_count: usize,
     span: usize,
 }
 
-fn replace_with_spans<'a>(
-    text: &'a str,
-    words: MatchedResult,
-    word_map: &HashMap<String, String>,
-) -> Cow<'a, str> {
-    let mut text_chars = text.chars().collect::<Vec<_>>();
-    let mut span_infos = Vec::with_capacity(words.len());
-    for (old, (old_chars_count, spans)) in words {
-        if let Some(new) = word_map.get(old) {
-            for span in spans {
-                // skip if the new chars are the same as the old chars
-                if text_chars[span..span + old_chars_count]
-                    .iter()
-                    .copied()
-                    .ne(new.chars())
-                {
+/// Find all matched words, with the plain words (Aho-Corasick) and the regex words (RegexSet).
+fn match_spans<'a>(text: &str, spellcheck: &'a SpellcheckConfig) -> Vec<SpanInfo<'a>> {
+    let mut span_infos = vec![];
+
+    for (matcher, word_map) in [
+        (&spellcheck.matcher, &spellcheck.word_map),
+        (
+            &spellcheck.case_sensitive_matcher,
+            &spellcheck.case_sensitive_word_map,
+        ),
+    ] {
+        for (old, (old_chars_count, spans)) in matcher.match_keywords(text) {
+            if let Some(entry) = word_map.get(old) {
+                for span in spans {
                     span_infos.push(SpanInfo {
-                        new,
+                        new: Cow::Borrowed(&entry.to),
+                        entry,
                         span,
                         old_chars_count,
                     });
@@ -48,48 +57,103 @@ fn replace_with_spans<'a>(
             }
         }
     }
-    span_infos.sort_unstable_by_key(|s| s.span);
 
-    let mut offset_change = 0;
+    for idx in spellcheck.regex_set.matches(text).iter() {
+        let (re, entry) = &spellcheck.regex_words[idx];
+        for caps in re.captures_iter(text) {
+            let m = caps.get(0).unwrap();
+            if m.is_empty() {
+                continue;
+            }
+
+            let mut new = String::new();
+            caps.expand(&entry.to, &mut new);
+
+            span_infos.push(SpanInfo {
+                new: Cow::Owned(new),
+                entry,
+                span: text[..m.start()].chars().count(),
+                old_chars_count: m.as_str().chars().count(),
+            });
+        }
+    }
+
+    span_infos
+}
+
+fn replace_with_spans<'a>(
+    text: &'a str,
+    mut span_infos: Vec<SpanInfo>,
+    severity: SeverityMode,
+    lint: bool,
+) -> (Cow<'a, str>, SeverityMode) {
+    // The longer word first, when they start at the same position.
+    span_infos.sort_unstable_by(|a, b| {
+        a.span
+            .cmp(&b.span)
+            .then(b.old_chars_count.cmp(&a.old_chars_count))
+    });
+
+    let text_chars = text.chars().collect::<Vec<_>>();
+    let mut out = String::with_capacity(text.len());
+    let mut cursor = 0;
     let mut changed = false;
+    let mut new_severity = SeverityMode::Warning;
 
     for span_info in span_infos.iter() {
-        let new_str = span_info.new;
-        let old_chars_count = span_info.old_chars_count;
         let span_start = span_info.span;
-        let span_end = span_info.span + old_chars_count;
+        let span_end = span_info.span + span_info.old_chars_count;
+        if span_start < cursor {
+            continue;
+        }
 
-        let offset_start = span_start + offset_change;
-        let offset_end = span_end + offset_change;
+        let word_severity = span_info.entry.severity.unwrap_or(severity);
+        match word_severity {
+            SeverityMode::Off => continue,
+            SeverityMode::Warning if !lint => continue,
+            _ => {}
+        }
 
         // Check whether the left and right 1 characters are allowed
         // If not allowed, skip this replacement
-        let l_c = if offset_start == 0 {
-            None
-        } else {
-            text_chars.get(offset_start - 1)
-        };
-        let r_c = text_chars.get(offset_end);
+        if span_info.entry.whole_word {
+            let l_c = if span_start == 0 {
+                None
+            } else {
+                text_chars.get(span_start - 1)
+            };
+            let r_c = text_chars.get(span_end);
 
-        if l_c.map(is_disallowed_char).unwrap_or_default()
-            || r_c.map(is_disallowed_char).unwrap_or_default()
-        {
-            // println!("---- `{:?}`|`{:?}`", l_c, r_c);
-            continue;
+            if l_c.map(is_disallowed_char).unwrap_or_default()
+                || r_c.map(is_disallowed_char).unwrap_or_default()
+            {
+                continue;
+            }
         }
 
-        // Perform replacement
-        text_chars.splice(offset_start..offset_end, new_str.chars());
+        // Keep the matched word, so the same word will not be matched by others
+        out.extend(&text_chars[cursor..span_start]);
+        out.push_str(&span_info.new);
+        cursor = span_end;
 
-        // Update offset_change due to length change after replacement
-        offset_change += new_str.chars().count() - old_chars_count;
-        changed = true;
+        // skip if the new chars are the same as the old chars
+        if text_chars[span_start..span_end]
+            .iter()
+            .copied()
+            .ne(span_info.new.chars())
+        {
+            changed = true;
+            if word_severity == SeverityMode::Error {
+                new_severity = SeverityMode::Error;
+            }
+        }
     }
 
     if changed {
-        Cow::Owned(text_chars.into_iter().collect::<String>())
+        out.extend(&text_chars[cursor..]);
+        (Cow::Owned(out), new_severity)
     } else {
-        Cow::Borrowed(text)
+        (Cow::Borrowed(text), severity)
     }
 }
 
@@ -186,17 +250,122 @@ mod tests {
         crate::config::setup_test();
 
         let words = Config::current().spellcheck.words.clone();
-        for l in words.iter() {
-            let (left, right) = if l.contains('=') {
-                let pars = l.split('=').collect::<Vec<_>>();
-                (pars[0].trim(), pars[1].trim())
-            } else {
-                (l.as_str(), l.as_str())
-            };
+        for word in words.iter() {
+            let entry = word.to_entry();
+            let (left, right) = (entry.from.as_str(), entry.to.as_str());
 
             assert_eq!(right, format(left));
             assert_eq!(right, format(&left.to_uppercase()));
             assert_eq!(right, format(&left.to_lowercase()));
         }
     }
+
+    fn assert_spellcheck_with_words(
+        words: Vec<crate::config::SpellcheckWord>,
+        lint: bool,
+        cases: HashMap<&str, (&str, SeverityMode)>,
+    ) {
+        let mut spellcheck = SpellcheckConfig {
+            words,
+            ..Default::default()
+        };
+        spellcheck.prepare();
+
+        for (source, (expected, expected_severity)) in cases.into_iter() {
+            let spans = match_spans(source, &spellcheck);
+            let (actual, severity) = replace_with_spans(source, spans, SeverityMode::Error, lint);
+            assert_eq!(expected, actual, "{}", source);
+            assert_eq!(expected_severity, severity, "{}", source);
+        }
+    }
+
+    fn entry(from: &str, to: &str) -> SpellcheckEntry {
+        SpellcheckEntry {
+            from: from.to_owned(),
+            to: to.to_owned(),
+            regex: false,
+            case_sensitive: false,
+            whole_word: true,
+            severity: None,
+        }
+    }
+
+    #[test]
+    fn test_spellcheck_case_sensitive() {
+        let words = vec![
+            crate::config::SpellcheckWord::Entry(SpellcheckEntry {
+                case_sensitive: true,
+                ..entry("Github", "GitHub")
+            }),
+            "ios = iOS".into(),
+        ];
+
+        let cases = map! [
+            "打开 Github 主页" => ("打开 GitHub 主页", SeverityMode::Error),
+            "打开 github.com 主页" => ("打开 github.com 主页", SeverityMode::Error),
+            "打开 github 主页" => ("打开 github 主页", SeverityMode::Error),
+            "打开 GITHUB 主页" => ("打开 GITHUB 主页", SeverityMode::Error),
+            "下载 IOS 版" => ("下载 iOS 版", SeverityMode::Error),
+        ];
+        assert_spellcheck_with_words(words, true, cases);
+    }
+
+    #[test]
+    fn test_spellcheck_regex() {
+        let words = vec![
+            crate::config::SpellcheckWord::Entry(SpellcheckEntry {
+                regex: true,
+                case_sensitive: true,
+                ..entry("i[Oo][Ss]", "iOS")
+            }),
+            crate::config::SpellcheckWord::Entry(SpellcheckEntry {
+                regex: true,
+                ..entry(r"mac\s?os\s?(\d+)", "macOS $1")
+            }),
+            crate::config::SpellcheckWord::Entry(SpellcheckEntry {
+                regex: true,
+                whole_word: false,
+                ..entry("javascript", "JavaScript")
+            }),
+        ];
+
+        let cases = map! [
+            "下载 ioS 版" => ("下载 iOS 版", SeverityMode::Error),
+            "下载 IOS 版" => ("下载 IOS 版", SeverityMode::Error),
+            "下载 iosapp 版" => ("下载 iosapp 版", SeverityMode::Error),
+            "升级到 MacOS 14 吧" => ("升级到 macOS 14 吧", SeverityMode::Error),
+            "升级到 mac os14 吧" => ("升级到 macOS 14 吧", SeverityMode::Error),
+            "学习 typescript-javascript 吧" => ("学习 typescript-JavaScript 吧", SeverityMode::Error),
+        ];
+        assert_spellcheck_with_words(words, true, cases);
+    }
+
+    #[test]
+    fn test_spellcheck_severity() {
+        let words = vec![
+            crate::config::SpellcheckWord::Entry(SpellcheckEntry {
+                severity: Some(SeverityMode::Warning),
+                ..entry("nodejs", "Node.js")
+            }),
+            "ios = iOS".into(),
+            crate::config::SpellcheckWord::Entry(SpellcheckEntry {
+                severity: Some(SeverityMode::Off),
+                ..entry("ios", "")
+            }),
+            "wifi = Wi-Fi".into(),
+        ];
+
+        let cases = map! [
+            "使用 nodejs 开发" => ("使用 Node.js 开发", SeverityMode::Warning),
+            "使用 nodejs 连接 wifi" => ("使用 Node.js 连接 Wi-Fi", SeverityMode::Error),
+            "下载 IOS 版" => ("下载 IOS 版", SeverityMode::Error),
+        ];
+        assert_spellcheck_with_words(words.clone(), true, cases);
+
+        let cases = map! [
+            "使用 nodejs 开发" => ("使用 nodejs 开发", SeverityMode::Error),
+            "使用 nodejs 连接 wifi" => ("使用 nodejs 连接 Wi-Fi", SeverityMode::Error),
+        ];
+        assert_spellcheck_with_words(words, false, cases);
+    }
 }
@@ -0,0 +1,177 @@
+// autocorrect: false
+use std::borrow::Cow;
+
+use crate::config::{Config, TerminologyConfig};
+
+/// A matched term in the text.
+struct Span<'a> {
+    /// Char index
+    start: usize,
+    /// Chars count
+    len: usize,
+    old: &'a str,
+    new: &'a str,
+}
+
+/// Find the terms to replace, the longer match has the higher priority.
+fn find_spans<'a>(chars: &[char], text: &str, terminology: &'a TerminologyConfig) -> Vec<Span<'a>> {
+    let matched = terminology.matcher.match_keywords(text);
+    if matched.is_empty() {
+        return vec![];
+    }
+
+    let mut candidates = matched
+        .iter()
+        .filter_map(|(old, (len, starts))| {
+            let (old, new) = terminology.word_map.get_key_value(*old)?;
+            Some(starts.iter().map(move |start| Span {
+                start: *start,
+                len: *len,
+                old,
+                new,
+            }))
+        })
+        .flatten()
+        .filter(|span| is_boundary(chars, span.start, span.len))
+        .collect::<Vec<_>>();
+    candidates.sort_unstable_by(|a, b| a.start.cmp(&b.start).then(b.len.cmp(&a.len)));
+
+    let mut spans = vec![];
+    let mut cursor = 0;
+    for span in candidates {
+        if span.start < cursor {
+            continue;
+        }
+
+        cursor = span.start + span.len;
+        spans.push(span);
+    }
+
+    spans
+}
+
+/// CJK has no word boundary, so only check the boundary when the term starts / ends with a Latin word char.
+///
+/// For example: `Wi-Fi热点` is matched with `热点`, but `Webhook` is not matched with `Web`.
+fn is_boundary(chars: &[char], start: usize, len: usize) -> bool {
+    let first = chars[start];
+    let last = chars[start + len - 1];
+
+    if is_word_char(&first) && start > 0 && is_word_char(&chars[start - 1]) {
+        return false;
+    }
+
+    if is_word_char(&last) && chars.get(start + len).map(is_word_char).unwrap_or(false) {
+        return false;
+    }
+
+    true
+}
+
+fn is_word_char(c: &char) -> bool {
+    c.is_ascii_alphanumeric() || *c == '_'
+}
+
+fn replace<'a>(text: &'a str, terminology: &TerminologyConfig) -> Cow<'a, str> {
+    let chars = text.chars().collect::<Vec<_>>();
+    let spans = find_spans(&chars, text, terminology);
+    if spans.is_empty() {
+        return Cow::Borrowed(text);
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut cursor = 0;
+    for span in spans {
+        out.extend(&chars[cursor..span.start]);
+        out.push_str(span.new);
+        cursor = span.start + span.len;
+    }
+    out.extend(&chars[cursor..]);
+
+    Cow::Owned(out)
+}
+
+fn lint_messages(text: &str, terminology: &TerminologyConfig) -> Vec<String> {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut messages: Vec<String> = vec![];
+    for span in find_spans(&chars, text, terminology) {
+        let message = format!("Prefer `{}` over `{}`", span.new, span.old);
+        if !messages.contains(&message) {
+            messages.push(message);
+        }
+    }
+
+    messages
+}
+
+/// Replace the regional terms by the `terminology` config of current `locale`.
+pub fn format(text: &str) -> Cow<'_, str> {
+    replace(text, &Config::current().terminology)
+}
+
+/// Lint messages to name the preferred terms, e.g.: "Prefer `資訊` over `信息`".
+pub fn messages(text: &str) -> Vec<String> {
+    lint_messages(text, &Config::current().terminology)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_boundary() {
+        let chars = "Webhook 和 Web 热点Wi-Fi".chars().collect::<Vec<_>>();
+        // Web in Webhook
+        assert!(!is_boundary(&chars, 0, 3));
+        // Web
+        assert!(is_boundary(&chars, 10, 3));
+        // 热点
+        assert!(is_boundary(&chars, 14, 2));
+        // Wi after 热点
+        assert!(is_boundary(&chars, 16, 2));
+        // hook in Webhook
+        assert!(!is_boundary(&chars, 3, 4));
+    }
+
+    fn test_config() -> TerminologyConfig {
+        let mut terminology = TerminologyConfig {
+            packs: vec!["it".to_owned()],
+            words: map! {
+                "zh-TW".to_owned() => vec!["Web = 網頁".to_owned()],
+            },
+            ..Default::default()
+        };
+        terminology.prepare(Some("zh-TW"));
+        terminology
+    }
+
+    #[test]
+    fn test_replace() {
+        let terminology = test_config();
+
+        let cases = map! {
+            "請檢查默认設定" => "請檢查預設設定",
+            "這個视频的信息" => "這個影片的資訊",
+            "查看數據庫和服务器" => "查看資料庫和伺服器",
+            "召開视频会议" => "召開視訊會議",
+            "打開Web頁面" => "打開網頁頁面",
+            "Webhook 設定" => "Webhook 設定",
+            "預設設定" => "預設設定",
+        };
+
+        for (source, expected) in cases {
+            assert_eq!(expected, replace(source, &terminology), "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_lint_messages() {
+        let terminology = test_config();
+
+        assert_eq!(
+            vec!["Prefer `資訊` over `信息`", "Prefer `預設` over `默认`"],
+            lint_messages("信息的默认值，其他信息", &terminology)
+        );
+        assert!(lint_messages("預設值", &terminology).is_empty());
+    }
+}
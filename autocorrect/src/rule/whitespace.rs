@@ -0,0 +1,113 @@
+// autocorrect: false
+use std::borrow::Cow;
+
+use super::CJK_RE;
+
+/// The invisible chars: zero width space, word joiner and BOM.
+const INVISIBLE_CHARS: [char; 3] = ['\u{200B}', '\u{2060}', '\u{FEFF}'];
+/// The spaces are not ASCII space: ideographic space and no-break space.
+const SPECIAL_SPACES: [char; 2] = ['\u{3000}', '\u{A0}'];
+
+/// Normalize the whitespaces near the CJK.
+///
+/// - The invisible chars near the CJK are removed, except the BOM at the start of the text.
+/// - The spaces between words (include U+3000, NBSP) are replaced by an ASCII space,
+///   the leading spaces (e.g.: the indent of the paragraph) and the trailing spaces are kept.
+pub fn format(text: &str) -> Cow<'_, str> {
+    if !text.contains(|c| is_invisible(c) || SPECIAL_SPACES.contains(&c)) {
+        return Cow::Borrowed(text);
+    }
+
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+
+        if is_invisible(c) {
+            let prev = out.chars().next_back();
+            let next = chars[i + 1..].iter().copied().find(|c| !is_invisible(*c));
+            let is_bom = i == 0 && c == '\u{FEFF}';
+            if is_bom || !(is_cjk(prev) || is_cjk(next)) {
+                out.push(c);
+            }
+            i += 1;
+            continue;
+        }
+
+        if is_space(c) {
+            let end = chars[i..]
+                .iter()
+                .position(|c| !is_space(*c))
+                .map_or(chars.len(), |n| i + n);
+            let spaces = &chars[i..end];
+            let prev = out.chars().next_back();
+            let next = chars.get(end).copied();
+
+            if spaces.iter().any(|c| SPECIAL_SPACES.contains(c))
+                && is_inline(prev)
+                && is_inline(next)
+                && (is_cjk(prev) || is_cjk(next))
+            {
+                out.push(' ');
+            } else {
+                out.extend(spaces);
+            }
+            i = end;
+            continue;
+        }
+
+        out.push(c);
+        i += 1;
+    }
+
+    if out == text {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(out)
+    }
+}
+
+fn is_invisible(c: char) -> bool {
+    INVISIBLE_CHARS.contains(&c)
+}
+
+fn is_space(c: char) -> bool {
+    c == ' ' || SPECIAL_SPACES.contains(&c)
+}
+
+/// The char is in the line, not the start or the end of line.
+fn is_inline(c: Option<char>) -> bool {
+    c.is_some_and(|c| !matches!(c, '\n' | '\r'))
+}
+
+fn is_cjk(c: Option<char>) -> bool {
+    c.is_some_and(|c| CJK_RE.is_match(c.encode_utf8(&mut [0; 4])))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        let cases = map! [
+            "你好\u{3000}世界" => "你好 世界",
+            "你好\u{A0}world" => "你好 world",
+            "Hello\u{3000}\u{3000}世界" => "Hello 世界",
+            "你好 \u{A0}世界" => "你好 世界",
+            "\u{3000}\u{3000}段落缩进" => "\u{3000}\u{3000}段落缩进",
+            "第一行\n\u{3000}\u{3000}缩进" => "第一行\n\u{3000}\u{3000}缩进",
+            "行尾\u{3000}" => "行尾\u{3000}",
+            "Hello\u{A0}world" => "Hello\u{A0}world",
+            "你好\u{200B}世界" => "你好世界",
+            "你好\u{FEFF}\u{2060}world" => "你好world",
+            "\u{FEFF}你好世界" => "\u{FEFF}你好世界",
+            "Hello\u{200B}world" => "Hello\u{200B}world",
+        ];
+
+        for (source, expected) in cases.into_iter() {
+            assert_eq!(expected, format(source), "{source:?}");
+        }
+    }
+}
@@ -43,7 +43,8 @@ lazy_static! {
     static ref DASH_STRATEGIES: Vec<Strategery> = vec![
         // Add space before and after dash - near the CJK
         Strategery::new(r"[\p{CJK_N}”’]", r"[\-][\p{CJK_N}\s（【「《“‘]"),
-        Strategery::new(r"[\p{CJK_N}\s）】」”’》][\-]", r"[\p{CJK_N}“‘]"),
+        // The dash at the start of a chunk is after a space, e.g.: `中文 -中文`
+        Strategery::new(r"(?:^|[\p{CJK_N}\s）】」”’》])[\-]", r"[\p{CJK_N}“‘]"),
     ];
 
     static ref DOLLAR_STRATEGIES: Vec<Strategery> = vec![
@@ -155,6 +156,9 @@ mod tests {
         assert_eq!(format_space_dash("foo-世界"), "foo-世界");
         assert_eq!(format_space_dash("你好-world"), "你好-world");
         assert_eq!(format_space_dash("hello-world"), "hello-world");
+        assert_eq!(format_space_dash("-世界"), "- 世界");
+        assert_eq!(format_space_dash("你好 -世界"), "你好 - 世界");
+        assert_eq!(format_space_dash("-world"), "-world");
     }
 
     #[test]
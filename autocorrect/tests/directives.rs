@@ -0,0 +1,80 @@
+use autocorrect::{lint_for, Severity};
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn test_report_unused_disable_directives() {
+    autocorrect::config::load("reportUnusedDisableDirectives: true").unwrap();
+
+    let raw = indoc! {r#"
+    // autocorrect-disable-next-line
+    const a = "hello世界";
+    // autocorrect-disable-next-line fullwidth
+    const b = "hello世界";
+    const c = "hello 世界"; // autocorrect-disable-line space-word, foo-bar
+    // autocorrect-disable
+    const d = "hello 世界";
+    // autocorrect-enable
+    // autocorrect-disable space-word
+    const e = "hello世界";
+    "#};
+
+    let result = lint_for(raw, "test.js");
+    let actual = result
+        .lines
+        .iter()
+        .map(|l| (l.line, l.severity, l.message.as_str()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        vec![
+            (4, Severity::Error, ""),
+            (3, Severity::Warning, "Unused directive, no problems were suppressed."),
+            (
+                5,
+                Severity::Warning,
+                "Unknown rule `foo-bar` in the directive.\nUnused directive, no problems were suppressed."
+            ),
+            (6, Severity::Warning, "Unused directive, no problems were suppressed."),
+            (
+                9,
+                Severity::Warning,
+                "The disabled region is still open at the end of file, use `autocorrect-enable` to close it."
+            ),
+        ],
+        actual
+    );
+
+    // Enable the other rules does not close the region
+    let raw = indoc! {r#"
+    // autocorrect-disable space-word
+    const a = "hello世界";
+    // autocorrect-enable fullwidth
+    "#};
+    let result = lint_for(raw, "test.js");
+    let actual = result
+        .lines
+        .iter()
+        .map(|l| (l.line, l.message.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![(
+            1,
+            "The disabled region is still open at the end of file, use `autocorrect-enable` to close it."
+        )],
+        actual
+    );
+
+    // The codeblock in Markdown is reported with the line number of the document
+    let raw = indoc! {r#"
+    # Title
+
+    ```js
+    // autocorrect-disable-next-line
+    const a = "hello 世界";
+    ```
+    "#};
+    let result = lint_for(raw, "test.md");
+    assert_eq!(1, result.lines.len());
+    assert_eq!(4, result.lines[0].line);
+}
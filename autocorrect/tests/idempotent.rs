@@ -0,0 +1,115 @@
+// autocorrect: false
+//! Property tests to find the inputs that the format is not idempotent.
+//!
+//! Run more cases with:
+//!
+//! ```bash
+//! PROPTEST_CASES=10000 cargo test --test idempotent
+//! ```
+use autocorrect::format_for_checked;
+use proptest::prelude::*;
+
+/// The text mixed with CJK, Latin, numbers, spaces and the punctuations, which the rules interact with.
+fn text() -> impl Strategy<Value = String> {
+    let token = prop_oneof![
+        Just("中文"),
+        Just("測試"),
+        Just("にほんご"),
+        Just("한국어"),
+        Just("Hello"),
+        Just("iOS"),
+        Just("100"),
+        Just("3.14"),
+        Just("%"),
+        Just(" "),
+        Just("  "),
+        Just(","),
+        Just("."),
+        Just("!"),
+        Just("?"),
+        Just(":"),
+        Just(";"),
+        Just("-"),
+        Just("..."),
+        Just("，"),
+        Just("。"),
+        Just("！"),
+        Just("？"),
+        Just("："),
+        Just("；"),
+        Just("、"),
+        Just("("),
+        Just(")"),
+        Just("（"),
+        Just("）"),
+        Just("「"),
+        Just("」"),
+        Just("“"),
+        Just("”"),
+        Just("'"),
+        Just("@"),
+        Just("#"),
+        Just("/"),
+    ];
+
+    prop::collection::vec(token, 1..16).prop_map(|tokens| tokens.concat())
+}
+
+/// Put the text in the places of the grammars, e.g.: the string and the comment of JavaScript.
+fn document() -> impl Strategy<Value = (&'static str, String)> {
+    (text(), text()).prop_flat_map(|(a, b)| {
+        prop_oneof![
+            Just(("text", format!("{a}\n{b}"))),
+            Just(("markdown", format!("# {a}\n\n{b}\n\n- {a}\n"))),
+            Just(("html", format!("<p>{a}</p>\n<div title=\"x\">{b}</div>\n"))),
+            Just(("javascript", format!("// {a}\nconst a = \"{b}\";\n"))),
+            Just(("python", format!("# {a}\na = '{b}'\n"))),
+            Just(("yaml", format!("a: {a}\nb: \"{b}\"\n"))),
+            Just(("json", format!("{{\"a\": \"{a}\"}}\n"))),
+            Just(("go", format!("// {a}\nvar a = \"{b}\"\n"))),
+        ]
+    })
+}
+
+proptest! {
+    #[test]
+    fn test_format_is_idempotent((filetype, raw) in document()) {
+        let result = format_for_checked(&raw, filetype, 2);
+        prop_assert!(
+            result.is_idempotent(),
+            "{}\n--- first pass\n{}\n--- second pass\n{}\n{}",
+            filetype,
+            result.result.out,
+            result.second.as_deref().unwrap_or_default(),
+            result.error,
+        );
+    }
+}
+
+/// The inputs found by the property tests.
+#[test]
+fn test_format_is_idempotent_regressions() {
+    let cases = [
+        ("text", "中文!한국어"),
+        ("text", "測試100 ：100，；」'\n/100/'"),
+        ("text", "」 Hello/測試, \n/？-3.14,）.iOS100?"),
+        ("json", "{\"a\": \"'」 Hello/測試, \"}\n"),
+        ("text", "中文\n3.14）  100/@：」測試3.14測試"),
+        ("text", "中文\nにほんご:測試:にほんご!@100」"),
+        (
+            "go",
+            "// 中文\nvar a = \"中文.  -にほんご)....”」Hello）\"\n",
+        ),
+    ];
+
+    for (filetype, raw) in cases {
+        let result = format_for_checked(raw, filetype, 2);
+        assert!(
+            result.is_idempotent(),
+            "{}\n--- first pass\n{}\n--- second pass\n{}",
+            raw,
+            result.result.out,
+            result.second.as_deref().unwrap_or_default(),
+        );
+    }
+}
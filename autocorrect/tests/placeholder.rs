@@ -0,0 +1,77 @@
+use std::borrow::Cow;
+
+use autocorrect::{format_for, lint_for, Context, Engine, Rule, Severity};
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+/// A rule breaks the placeholders, the format must stop.
+struct AddPlaceholder;
+
+impl Rule for AddPlaceholder {
+    fn name(&self) -> &str {
+        "add-placeholder"
+    }
+
+    fn format<'a>(&self, input: &'a str, _context: &Context) -> Cow<'a, str> {
+        if input.contains("文件") {
+            Cow::Owned(input.replace("文件", "{file}"))
+        } else {
+            Cow::Borrowed(input)
+        }
+    }
+}
+
+#[test]
+fn test_placeholders() {
+    let raw = indoc! {r#"
+    a: 共%lld个file
+    b: "你好%{user}，共{{ count }}个item"
+    c: "{n, plural, one {#个item} other {#个items}}"
+    d: 点击<b>这里</b>查看%1$@的file
+    "#};
+    let expected = indoc! {r#"
+    a: 共%lld个 file
+    b: "你好%{user}，共{{ count }}个 item"
+    c: "{n, plural, one {#个item} other {#个items}}"
+    d: 点击<b>这里</b>查看%1$@的 file
+    "#};
+    assert_eq!(expected, format_for(raw, "yaml").out);
+
+    Engine::register_rule(AddPlaceholder);
+
+    let raw = indoc! {r#"
+    a: 你好world
+    b: 共%d个文件
+    "#};
+    // Only the line is kept, the others are still formatted
+    let result = format_for(raw, "yaml");
+    assert_eq!("a: 你好 world\nb: 共%d个文件\n", result.out);
+    assert_eq!("", result.error);
+
+    let result = lint_for(raw, "yaml");
+    let line = result.lines.last().unwrap();
+    assert_eq!((2, Severity::Error), (line.line, line.severity));
+    assert_eq!("共%d个文件", line.new);
+    assert_eq!(
+        "The placeholder `{file}` would be changed, the text is kept.",
+        line.message
+    );
+
+    Engine::unregister_rule("add-placeholder");
+}
+
+#[test]
+fn test_placeholders_only_for_i18n_files() {
+    assert_eq!(
+        "a: 共%lld个 file\n",
+        format_for("a: 共%lld个file\n", "yaml").out
+    );
+    assert_eq!(
+        r#"{"a": "共%lld个 file"}"#,
+        format_for(r#"{"a": "共%lld个file"}"#, "json").out
+    );
+
+    // The other files are formatted as before
+    assert_eq!("共%lld 个 file", format_for("共%lld个file", "md").out);
+    assert_eq!("共%lld 个 file", format_for("共%lld个file", "text").out);
+}